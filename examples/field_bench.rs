@@ -0,0 +1,52 @@
+// cargo run --release --example field_bench
+use shamir_share::{Field, ModInt, MontInt};
+use std::time::{Duration, Instant};
+
+const MOD: usize = 2_305_843_009_213_693_951; // 2^61 - 1
+const PARTIES: u16 = 1000;
+const DEGREE: u16 = 300;
+
+// x^k を逐次掛ける素朴な評価で全員分のシェアを作り、
+// 先頭DEGREE+1人分のLagrange係数を定義どおりの積で計算する (体の四則演算の比較用)
+fn workload<T>() -> (Duration, T)
+where
+    T: Field,
+{
+    let start = Instant::now();
+
+    let rands: Vec<T> = (1..=DEGREE).map(T::from).collect();
+    let mut acc = T::zero();
+    for id in 1..=PARTIES {
+        let x: T = id.into();
+        let mut res = T::from(42);
+        let mut xn = x;
+        for &r in rands.iter() {
+            res += r * xn;
+            xn *= x;
+        }
+        acc += res;
+    }
+
+    for p in 1..=(DEGREE + 1) {
+        let p: T = p.into();
+        let mut r = T::one();
+        for q in 1..=(DEGREE + 1) {
+            let q: T = q.into();
+            if p != q {
+                r *= (T::zero() - q) / (p - q);
+            }
+        }
+        acc += r;
+    }
+
+    (start.elapsed(), acc)
+}
+
+fn main() {
+    let (t_mod, r_mod) = workload::<ModInt<MOD>>();
+    let (t_mont, r_mont) = workload::<MontInt<MOD>>();
+    assert_eq!(r_mod, ModInt::from(r_mont));
+
+    println!("ModInt : {:?}", t_mod);
+    println!("MontInt: {:?}", t_mont);
+}
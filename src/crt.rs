@@ -0,0 +1,195 @@
+use crate::{add_mod, inv_mod, mul_mod, sub_mod, ModInt};
+use num_traits::identities::{One, Zero};
+use std::ops;
+
+// x ≡ residues[i] (mod moduli[i]) となる最小の x >= 0。
+// 法が互いに素でない、または x が u128 に収まらなければNone
+pub fn crt(residues: &[usize], moduli: &[usize]) -> Option<u128> {
+    assert_eq!(
+        residues.len(),
+        moduli.len(),
+        "residues and moduli differ in length"
+    );
+
+    // Garnerのアルゴリズムで x = v_0 + v_1 m_0 + v_2 m_0 m_1 + ... の各桁を求める
+    let mut digits: Vec<usize> = Vec::with_capacity(moduli.len());
+    for (i, (&r, &m)) in residues.iter().zip(moduli.iter()).enumerate() {
+        let mut acc = 0; // v_0 + v_1 m_0 + ... (mod m)
+        let mut prod = 1 % m; // m_0 ... m_{i-1} (mod m)
+        for (&v, &mj) in digits.iter().zip(moduli[..i].iter()) {
+            acc = add_mod(acc, mul_mod(v, prod, m), m);
+            prod = mul_mod(prod, mj % m, m);
+        }
+        let inv = inv_mod(prod, m)?;
+        digits.push(mul_mod(sub_mod(r % m, acc, m), inv, m));
+    }
+
+    // 上の桁からHorner法で組み立てる
+    let mut x: u128 = 0;
+    for (&v, &m) in digits.iter().zip(moduli.iter()).rev() {
+        x = x.checked_mul(m as u128)?.checked_add(v as u128)?;
+    }
+    Some(x)
+}
+
+pub fn crt_split(x: u128, moduli: &[usize]) -> Vec<usize> {
+    moduli.iter().map(|&m| (x % m as u128) as usize).collect()
+}
+
+// 法の積。u128 に収まらなければNone
+pub fn crt_product(moduli: &[usize]) -> Option<u128> {
+    moduli
+        .iter()
+        .try_fold(1u128, |acc, &m| acc.checked_mul(m as u128))
+}
+
+// 各成分を別々の法で持つ剰余数系の値。成分ごとに独立に計算・シェアできる
+macro_rules! define_rns {
+    ($name:ident; $($p:ident $r:ident),+) => {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        pub struct $name<$(const $p: usize),+> {
+            $(pub $r: ModInt<$p>,)+
+        }
+
+        impl<$(const $p: usize),+> $name<$($p),+> {
+            pub fn new($($r: ModInt<$p>),+) -> Self {
+                Self { $($r),+ }
+            }
+
+            pub fn moduli() -> Vec<usize> {
+                vec![$($p),+]
+            }
+
+            pub fn modulus() -> Option<u128> {
+                crt_product(&Self::moduli())
+            }
+
+            pub fn from_u128(x: u128) -> Self {
+                Self { $($r: ModInt::from(x)),+ }
+            }
+
+            // 法の積を法とした値。u128 に収まらなければNone
+            pub fn to_u128(&self) -> Option<u128> {
+                crt(&self.residues(), &Self::moduli())
+            }
+
+            pub fn residues(&self) -> Vec<usize> {
+                vec![$(self.$r.val()),+]
+            }
+        }
+
+        impl<$(const $p: usize),+> ops::Add for $name<$($p),+> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($r: self.$r + other.$r),+ }
+            }
+        }
+
+        impl<$(const $p: usize),+> ops::Sub for $name<$($p),+> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($r: self.$r - other.$r),+ }
+            }
+        }
+
+        impl<$(const $p: usize),+> ops::Mul for $name<$($p),+> {
+            type Output = Self;
+
+            fn mul(self, other: Self) -> Self {
+                Self { $($r: self.$r * other.$r),+ }
+            }
+        }
+
+        impl<$(const $p: usize),+> ops::Neg for $name<$($p),+> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($r: -self.$r),+ }
+            }
+        }
+
+        impl<$(const $p: usize),+> Zero for $name<$($p),+> {
+            fn zero() -> Self {
+                Self { $($r: ModInt::zero()),+ }
+            }
+
+            fn is_zero(&self) -> bool {
+                $(self.$r.is_zero())&&+
+            }
+        }
+
+        impl<$(const $p: usize),+> One for $name<$($p),+> {
+            fn one() -> Self {
+                Self { $($r: ModInt::one()),+ }
+            }
+        }
+    };
+}
+
+define_rns!(Rns2; P1 r1, P2 r2);
+define_rns!(Rns3; P1 r1, P2 r2, P3 r3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[2, 3, 2], &[3, 5, 7]), Some(23));
+        assert_eq!(crt(&[1, 2], &[4, 6]), None);
+        assert_eq!(crt(&[], &[]), Some(0));
+        assert_eq!(crt_split(23, &[3, 5, 7]), vec![2, 3, 2]);
+
+        let moduli = [2_305_843_009_213_693_951, 18_446_744_073_709_551_557];
+        let product = crt_product(&moduli).unwrap();
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let x = rng.gen_range(0..product);
+            assert_eq!(crt(&crt_split(x, &moduli), &moduli), Some(x));
+        }
+
+        // 3つの64bit素数の積は u128 を超えるが、値が収まれば復元できる
+        let moduli = [
+            18_446_744_073_709_551_557,
+            18_446_744_073_709_551_533,
+            18_446_744_073_709_551_521,
+        ];
+        assert_eq!(crt_product(&moduli), None);
+        assert_eq!(
+            crt(&crt_split(u128::MAX, &moduli), &moduli),
+            Some(u128::MAX)
+        );
+        assert_eq!(crt(&[1, 2, 3], &moduli), None);
+
+        // m - 1 に近い剰余でも途中の和で桁あふれしない (x = 積 - 1 - i は u128 に収まらない)
+        for i in 0..50 {
+            let residues: Vec<usize> = moduli.iter().map(|&m| m - 1 - i).collect();
+            assert_eq!(crt(&residues, &moduli), None);
+        }
+        for _ in 0..1000 {
+            let x = rng.gen::<u128>();
+            assert_eq!(crt(&crt_split(x, &moduli), &moduli), Some(x));
+        }
+    }
+
+    #[test]
+    fn test_rns() {
+        type R = Rns2<1_000_000_007, 998_244_353>;
+        let m = R::modulus().unwrap();
+        let (a, b) = (123_456_789_012_345_678u128, 987_654_321_098_765u128);
+        let (ra, rb) = (R::from_u128(a), R::from_u128(b));
+        assert_eq!((ra + rb).to_u128(), Some((a + b) % m));
+        assert_eq!((ra - rb).to_u128(), Some((a - b) % m));
+        assert_eq!((ra * rb).to_u128(), Some((a % m) * (b % m) % m));
+        assert_eq!((-ra + ra), R::zero());
+        assert_eq!(R::one().to_u128(), Some(1));
+
+        type R3 = Rns3<2_147_483_647, 2_305_843_009_213_693_951, 998_244_353>;
+        let x = (1u128 << 120) + 12345;
+        assert_eq!(R3::from_u128(x).to_u128(), Some(x));
+        assert_eq!(R3::from_u128(x).residues().len(), 3);
+    }
+}
@@ -0,0 +1,343 @@
+use crate::montgomery::MontParams;
+use crate::{field, Field, ModInt, ParseModIntError};
+use num_traits::{
+    identities::{One, Zero},
+    Num,
+};
+use rand::Rng;
+use std::convert::{From, Into};
+use std::fmt;
+use std::hint::black_box;
+use std::iter;
+use std::ops;
+
+// 秘密の値に依存する分岐と除算命令を使わないMontgomery表現 (R = 2^64)。MODは奇数
+#[derive(Copy, Clone)]
+pub struct CtModInt<const MOD: usize> {
+    val: u64, // val = x * R mod MOD
+}
+
+// bitが1なら全ビット1、0なら0
+fn mask(bit: u64) -> u64 {
+    black_box(0u64.wrapping_sub(bit & 1))
+}
+
+// x < 2 * MOD を [0, MOD) に落とす
+fn reduce_once(x: u128, m: u64) -> u64 {
+    let t = x.wrapping_sub(m as u128);
+    let borrow = mask((t >> 127) as u64);
+    (t as u64 & !borrow) | (x as u64 & borrow)
+}
+
+fn ct_add(a: u64, b: u64, m: u64) -> u64 {
+    reduce_once(a as u128 + b as u128, m)
+}
+
+fn ct_sub(a: u64, b: u64, m: u64) -> u64 {
+    let t = (a as u128).wrapping_sub(b as u128);
+    let borrow = mask((t >> 127) as u64);
+    (t as u64).wrapping_add(m & borrow)
+}
+
+impl<const MOD: usize> CtModInt<MOD> {
+    const WINDOW: u32 = 4;
+
+    // t * R^{-1} mod MOD (t < MOD * 2^64)
+    fn redc(t: u128) -> u64 {
+        let (hi, mn) = MontParams::<MOD>::redc_parts(t);
+        ct_sub(hi, mn, MOD as u64)
+    }
+
+    // n < 2^64 なら n * R2 < MOD * 2^64 なので除算なしで変換できる
+    pub fn new(n: u64) -> Self {
+        Self {
+            val: Self::redc(n as u128 * MontParams::<MOD>::R2 as u128),
+        }
+    }
+
+    pub fn val(&self) -> u64 {
+        Self::redc(self.val as u128)
+    }
+
+    // choiceがtrueならb、falseならa
+    pub fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        let m = mask(choice as u64);
+        Self {
+            val: (a.val & !m) | (b.val & m),
+        }
+    }
+
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        let x = self.val ^ other.val;
+        // x == 0 のときだけ最上位ビットが立つ
+        let is_zero = (!x & x.wrapping_sub(1)) >> 63;
+        black_box(is_zero) == 1
+    }
+
+    // 4bitずつの固定窓。指数の値によらず同じ回数の乗算と表の全走査を行う
+    pub fn pow_u(&self, n: u64) -> Self {
+        let size = 1 << Self::WINDOW;
+        let mut table = [Self::one(); 16];
+        for i in 1..size {
+            table[i] = table[i - 1] * *self;
+        }
+
+        let mut res = Self::one();
+        for k in (0..64 / Self::WINDOW).rev() {
+            for _ in 0..Self::WINDOW {
+                res = res * res;
+            }
+            let digit = (n >> (k * Self::WINDOW)) & (size as u64 - 1);
+            let mut entry = Self::one();
+            for (i, t) in table.iter().enumerate() {
+                entry = Self::conditional_select(&entry, t, digit == i as u64);
+            }
+            res *= entry;
+        }
+        res
+    }
+
+    pub fn pow(&self, other: Self) -> Self {
+        self.pow_u(other.val())
+    }
+
+    pub fn inv(&self) -> Self {
+        self.pow_u(MOD as u64 - 2)
+    }
+}
+
+impl<const MOD: usize> Field for CtModInt<MOD> {
+    fn checked_inv(&self) -> Option<Self> {
+        let () = ModInt::<MOD>::ASSERT_PRIME;
+        if self.is_zero() {
+            None
+        } else {
+            Some(CtModInt::inv(self))
+        }
+    }
+
+    fn characteristic() -> Vec<u8> {
+        ModInt::<MOD>::characteristic()
+    }
+
+    // 棄却の回数は値と独立
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::from(ModInt::<MOD>::random(rng))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        ModInt::<MOD>::from(*self).to_bytes()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        ModInt::<MOD>::from_bytes(bytes).map(Self::from)
+    }
+}
+
+impl<const MOD: usize> PartialEq for CtModInt<MOD> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl<const MOD: usize> Eq for CtModInt<MOD> {}
+
+impl<const MOD: usize> Num for CtModInt<MOD> {
+    type FromStrRadixErr = ParseModIntError;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        ModInt::<MOD>::from_str_radix(str, radix).map(Self::from)
+    }
+}
+
+impl<const MOD: usize> fmt::Display for CtModInt<MOD> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.val())
+    }
+}
+
+impl<const MOD: usize> fmt::Debug for CtModInt<MOD> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CtModInt {{ val: {} }}", self.val())
+    }
+}
+
+impl<const MOD: usize, I> From<I> for CtModInt<MOD>
+where
+    I: Into<u64>,
+{
+    fn from(n: I) -> Self {
+        Self::new(n.into())
+    }
+}
+
+impl<const MOD: usize> From<ModInt<MOD>> for CtModInt<MOD> {
+    fn from(n: ModInt<MOD>) -> Self {
+        Self::new(n.val() as u64)
+    }
+}
+
+// val() は MOD 未満なので、秘密の値に対して除算命令を使わないよう簡約せずに作る
+impl<const MOD: usize> From<CtModInt<MOD>> for ModInt<MOD> {
+    fn from(n: CtModInt<MOD>) -> Self {
+        ModInt {
+            val: n.val() as usize,
+        }
+    }
+}
+
+impl<const MOD: usize> ops::Add for CtModInt<MOD> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            val: ct_add(self.val, other.val, MOD as u64),
+        }
+    }
+}
+
+impl<const MOD: usize> ops::AddAssign for CtModInt<MOD> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const MOD: usize> ops::Neg for CtModInt<MOD> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            val: ct_sub(0, self.val, MOD as u64),
+        }
+    }
+}
+
+impl<const MOD: usize> ops::Sub for CtModInt<MOD> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            val: ct_sub(self.val, other.val, MOD as u64),
+        }
+    }
+}
+
+impl<const MOD: usize> ops::SubAssign for CtModInt<MOD> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const MOD: usize> ops::Mul for CtModInt<MOD> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            val: Self::redc(self.val as u128 * other.val as u128),
+        }
+    }
+}
+
+impl<const MOD: usize> ops::MulAssign for CtModInt<MOD> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<const MOD: usize> ops::Div for CtModInt<MOD> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        if other.is_zero() {
+            panic!("0 division occured.");
+        }
+
+        self * other.inv()
+    }
+}
+
+impl<const MOD: usize> ops::DivAssign for CtModInt<MOD> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+field::impl_field_rem!([const MOD: usize] CtModInt<MOD>);
+
+impl<const MOD: usize> Zero for CtModInt<MOD> {
+    fn zero() -> Self {
+        Self { val: 0 }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.ct_eq(&Self::zero())
+    }
+}
+
+impl<const MOD: usize> One for CtModInt<MOD> {
+    fn one() -> Self {
+        Self {
+            val: MontParams::<MOD>::R1,
+        }
+    }
+
+    fn is_one(&self) -> bool {
+        self.ct_eq(&Self::one())
+    }
+}
+
+impl<const MOD: usize> iter::Sum for CtModInt<MOD> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<const MOD: usize> iter::Product for CtModInt<MOD> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_modint() {
+        fn check<const MOD: usize>(xs: &[u64]) {
+            for &a in xs {
+                for &b in xs {
+                    let (ca, cb) = (CtModInt::<MOD>::new(a), CtModInt::<MOD>::new(b));
+                    let (ma, mb) = (ModInt::<MOD>::from(a), ModInt::<MOD>::from(b));
+                    assert_eq!((ca + cb).val() as usize, (ma + mb).val());
+                    assert_eq!((ca - cb).val() as usize, (ma - mb).val());
+                    assert_eq!((ca * cb).val() as usize, (ma * mb).val());
+                    assert_eq!(ca.pow_u(b).val() as usize, ma.pow_u(b as usize).val());
+                    if !mb.is_zero() {
+                        assert_eq!((ca / cb).val() as usize, (ma / mb).val());
+                    }
+                    assert_eq!(ca == cb, ma == mb);
+                }
+            }
+        }
+        check::<17>(&[0, 1, 2, 5, 16, 17, 100, u64::MAX]);
+        check::<1_000_000_007>(&[0, 1, 999_999_999, 1 << 40, u64::MAX]);
+        check::<18_446_744_073_709_551_557>(&[0, 1, 1 << 63, u64::MAX - 59, u64::MAX]);
+    }
+
+    #[test]
+    fn test_select_and_eq() {
+        type C = CtModInt<1_000_000_007>;
+        let (a, b) = (C::new(3), C::new(5));
+        assert_eq!(C::conditional_select(&a, &b, false), a);
+        assert_eq!(C::conditional_select(&a, &b, true), b);
+        assert!(a.ct_eq(&C::new(1_000_000_010)));
+        assert!(!a.ct_eq(&b));
+        assert!((a * a.inv()).is_one());
+        assert!((a - a).is_zero());
+        assert!((a % b).is_zero());
+        assert!(std::panic::catch_unwind(|| a % C::zero()).is_err());
+        assert_eq!(C::from_bytes(&a.to_bytes()), Some(a));
+    }
+}
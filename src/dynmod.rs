@@ -0,0 +1,640 @@
+use crate::{
+    add_mod, field, inv_mod, is_prime, mul_mod, sample_below, sub_mod, Field, ParseModIntError,
+};
+use num_traits::{
+    identities::{One, Zero},
+    Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, Inv, Num, Pow,
+    ToPrimitive,
+};
+use rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformSampler};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+use std::convert::{From, TryFrom};
+use std::fmt;
+use std::hash::Hash;
+use std::iter;
+use std::marker::PhantomData;
+use std::num::TryFromIntError;
+use std::ops;
+use std::str;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// 実行時に決まる法を型ごとに1つ保持する文脈。mod_context! で定義する
+pub trait ModContext:
+    fmt::Debug + Copy + Clone + PartialEq + Eq + PartialOrd + Ord + Hash + Send + Sync + 'static
+{
+    fn modulus_cell() -> &'static AtomicUsize;
+
+    fn modulus() -> usize {
+        match Self::modulus_cell().load(Ordering::Acquire) {
+            0 => panic!("modulus of {} is not set", std::any::type_name::<Self>()),
+            m => m,
+        }
+    }
+
+    fn try_modulus() -> Option<usize> {
+        match Self::modulus_cell().load(Ordering::Acquire) {
+            0 => None,
+            m => Some(m),
+        }
+    }
+
+    // 既存の元が壊れないよう、一度決めた法は別の値に変えられない
+    fn set_modulus(m: usize) {
+        if !is_prime(m) {
+            panic!("{} is not prime", m);
+        }
+        if let Err(old) =
+            Self::modulus_cell().compare_exchange(0, m, Ordering::AcqRel, Ordering::Acquire)
+        {
+            if old != m {
+                panic!(
+                    "modulus of {} is already set to {}",
+                    std::any::type_name::<Self>(),
+                    old
+                );
+            }
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! mod_context {
+    ($vis:vis $name:ident) => {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name;
+
+        impl $crate::ModContext for $name {
+            fn modulus_cell() -> &'static ::std::sync::atomic::AtomicUsize {
+                static MODULUS: ::std::sync::atomic::AtomicUsize =
+                    ::std::sync::atomic::AtomicUsize::new(0);
+                &MODULUS
+            }
+        }
+    };
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DynModInt<C: ModContext> {
+    val: usize,
+    ctx: PhantomData<C>,
+}
+
+impl<C: ModContext> DynModInt<C> {
+    fn raw(val: usize) -> Self {
+        Self {
+            val,
+            ctx: PhantomData,
+        }
+    }
+
+    pub fn modulus() -> usize {
+        C::modulus()
+    }
+
+    pub fn new(n: usize) -> Self {
+        Self::raw(n % C::modulus())
+    }
+
+    pub fn val(&self) -> usize {
+        self.val
+    }
+
+    // (-modulus/2, modulus/2] の代表元
+    pub fn to_signed(&self) -> i64 {
+        let m = C::modulus();
+        if self.val > m / 2 {
+            -((m - self.val) as i64)
+        } else {
+            self.val as i64
+        }
+    }
+
+    pub fn pow_u(&self, mut n: usize) -> Self {
+        let m = C::modulus();
+        let mut val = self.val;
+        let mut res = 1;
+        while n > 0 {
+            if n % 2 == 1 {
+                res = mul_mod(res, val, m);
+            }
+            val = mul_mod(val, val, m);
+            n /= 2;
+        }
+
+        Self::raw(res)
+    }
+
+    pub fn pow(&self, other: Self) -> Self {
+        self.pow_u(other.val)
+    }
+
+    pub fn inv(&self) -> Self {
+        match self.checked_inv() {
+            Some(res) => res,
+            None => panic!("{} is not invertible modulo {}.", self.val, C::modulus()),
+        }
+    }
+
+    pub fn checked_inv(&self) -> Option<Self> {
+        inv_mod(self.val, C::modulus()).map(Self::raw)
+    }
+}
+
+impl<C: ModContext> Field for DynModInt<C> {
+    fn checked_inv(&self) -> Option<Self> {
+        DynModInt::checked_inv(self)
+    }
+
+    fn characteristic() -> Vec<u8> {
+        field::trim_be_bytes(&(C::modulus() as u128).to_be_bytes())
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        rng.gen()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let len = field::byte_len(C::modulus() as u128);
+        field::to_be_fixed(self.val as u128, len)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let m = C::modulus();
+        let val = field::from_be_fixed(bytes, field::byte_len(m as u128))?;
+        if val < m as u128 {
+            Some(Self::raw(val as usize))
+        } else {
+            None
+        }
+    }
+}
+
+impl<C: ModContext> Distribution<DynModInt<C>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> DynModInt<C> {
+        DynModInt::raw(sample_below(rng, C::modulus()))
+    }
+}
+
+// 代表元 0..modulus の順序での区間からの一様分布
+#[derive(Debug, Copy, Clone)]
+pub struct UniformDynModInt<C: ModContext> {
+    low: usize,
+    range: usize,
+    ctx: PhantomData<C>,
+}
+
+impl<C: ModContext> UniformSampler for UniformDynModInt<C> {
+    type X = DynModInt<C>;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high) = (low.borrow().val, high.borrow().val);
+        assert!(
+            low < high,
+            "UniformDynModInt::new called with `low >= high`"
+        );
+        Self {
+            low,
+            range: high - low,
+            ctx: PhantomData,
+        }
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high) = (low.borrow().val, high.borrow().val);
+        assert!(
+            low <= high,
+            "UniformDynModInt::new_inclusive called with `low > high`"
+        );
+        Self {
+            low,
+            range: high - low + 1,
+            ctx: PhantomData,
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        DynModInt::raw(self.low + sample_below(rng, self.range))
+    }
+}
+
+impl<C: ModContext> SampleUniform for DynModInt<C> {
+    type Sampler = UniformDynModInt<C>;
+}
+
+impl<C: ModContext> Num for DynModInt<C> {
+    type FromStrRadixErr = ParseModIntError;
+
+    // ModInt と同じく 0..modulus 以外の値は受け付けない
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        let val = usize::from_str_radix(str, radix).map_err(ParseModIntError::Int)?;
+        if val < C::modulus() {
+            Ok(Self::raw(val))
+        } else {
+            Err(ParseModIntError::OutOfRange)
+        }
+    }
+}
+
+impl<C: ModContext> str::FromStr for DynModInt<C> {
+    type Err = ParseModIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+impl<C: ModContext> fmt::Display for DynModInt<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+impl<C: ModContext> fmt::Debug for DynModInt<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DynModInt {{ val: {} }}", self.val)
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl<C: ModContext> From<$t> for DynModInt<C> {
+                fn from(n: $t) -> Self {
+                    Self::raw((n as u128 % C::modulus() as u128) as usize)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl<C: ModContext> From<$t> for DynModInt<C> {
+                fn from(n: $t) -> Self {
+                    Self::raw((n as i128).rem_euclid(C::modulus() as i128) as usize)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl<C: ModContext> From<DynModInt<C>> for i64 {
+    fn from(n: DynModInt<C>) -> Self {
+        n.to_signed()
+    }
+}
+
+// to_signed() の値が収まらなければエラー
+macro_rules! impl_try_into_signed {
+    ($($t:ty),*) => {
+        $(
+            impl<C: ModContext> TryFrom<DynModInt<C>> for $t {
+                type Error = TryFromIntError;
+
+                fn try_from(n: DynModInt<C>) -> Result<Self, Self::Error> {
+                    <$t>::try_from(n.to_signed())
+                }
+            }
+        )*
+    };
+}
+
+impl_try_into_signed!(i8, i16, i32);
+
+impl<C: ModContext> ops::Add for DynModInt<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::raw(add_mod(self.val, other.val, C::modulus()))
+    }
+}
+
+impl<C: ModContext> ops::AddAssign for DynModInt<C> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<C: ModContext> ops::Neg for DynModInt<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::raw(sub_mod(0, self.val, C::modulus()))
+    }
+}
+
+impl<C: ModContext> ops::Sub for DynModInt<C> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::raw(sub_mod(self.val, other.val, C::modulus()))
+    }
+}
+
+impl<C: ModContext> ops::SubAssign for DynModInt<C> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<C: ModContext> ops::Mul for DynModInt<C> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::raw(mul_mod(self.val, other.val, C::modulus()))
+    }
+}
+
+impl<C: ModContext> ops::MulAssign for DynModInt<C> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<C: ModContext> ops::Div for DynModInt<C> {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other.val == 0 {
+            panic!("0 division occured.");
+        }
+
+        self * other.inv()
+    }
+}
+
+impl<C: ModContext> ops::DivAssign for DynModInt<C> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+field::impl_field_rem!([C: ModContext] DynModInt<C>);
+
+impl<C: ModContext> Zero for DynModInt<C> {
+    fn zero() -> Self {
+        Self::raw(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.val == 0
+    }
+}
+
+impl<C: ModContext> One for DynModInt<C> {
+    fn one() -> Self {
+        Self::raw(1)
+    }
+
+    fn is_one(&self) -> bool {
+        self.val == 1
+    }
+}
+
+impl<C: ModContext> iter::Sum for DynModInt<C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a, C: ModContext> iter::Sum<&'a DynModInt<C>> for DynModInt<C> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, &x| acc + x)
+    }
+}
+
+impl<C: ModContext> iter::Product for DynModInt<C> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a, C: ModContext> iter::Product<&'a DynModInt<C>> for DynModInt<C> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, &x| acc * x)
+    }
+}
+
+impl<C: ModContext> Pow<usize> for DynModInt<C> {
+    type Output = Self;
+
+    fn pow(self, n: usize) -> Self {
+        self.pow_u(n)
+    }
+}
+
+impl<C: ModContext> Pow<u32> for DynModInt<C> {
+    type Output = Self;
+
+    fn pow(self, n: u32) -> Self {
+        self.pow_u(n as usize)
+    }
+}
+
+impl<C: ModContext> Pow<DynModInt<C>> for DynModInt<C> {
+    type Output = Self;
+
+    fn pow(self, other: Self) -> Self {
+        self.pow_u(other.val)
+    }
+}
+
+impl<C: ModContext> Inv for DynModInt<C> {
+    type Output = Self;
+
+    fn inv(self) -> Self {
+        DynModInt::inv(&self)
+    }
+}
+
+// 代表元 0..modulus の範囲
+impl<C: ModContext> Bounded for DynModInt<C> {
+    fn min_value() -> Self {
+        Self::raw(0)
+    }
+
+    fn max_value() -> Self {
+        Self::raw(C::modulus() - 1)
+    }
+}
+
+// 剰余演算なので加減乗算は溢れない。除算は法と互いに素でなければNone
+impl<C: ModContext> CheckedAdd for DynModInt<C> {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(*self + *other)
+    }
+}
+
+impl<C: ModContext> CheckedSub for DynModInt<C> {
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(*self - *other)
+    }
+}
+
+impl<C: ModContext> CheckedMul for DynModInt<C> {
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(*self * *other)
+    }
+}
+
+impl<C: ModContext> CheckedDiv for DynModInt<C> {
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.checked_inv().map(|inv| *self * inv)
+    }
+}
+
+impl<C: ModContext> FromPrimitive for DynModInt<C> {
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(Self::from(n))
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(Self::from(n))
+    }
+
+    fn from_i128(n: i128) -> Option<Self> {
+        Some(Self::from(n))
+    }
+
+    fn from_u128(n: u128) -> Option<Self> {
+        Some(Self::from(n))
+    }
+
+    // 整数でない値は体の元に対応しないのでNone
+    fn from_f64(n: f64) -> Option<Self> {
+        if n.fract() != 0.0 {
+            return None;
+        }
+        i128::from_f64(n).map(Self::from)
+    }
+
+    fn from_f32(n: f32) -> Option<Self> {
+        Self::from_f64(n as f64)
+    }
+}
+
+// 符号なしは代表元 0..modulus、符号付きは i64::from と同じく to_signed の値を変換する
+impl<C: ModContext> ToPrimitive for DynModInt<C> {
+    fn to_i64(&self) -> Option<i64> {
+        Some(self.to_signed())
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.val.to_u64()
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        Some(self.to_signed() as i128)
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        Some(self.val as u128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lagrange_coefficients, ModInt};
+
+    mod_context!(Ctx17);
+    mod_context!(CtxBig);
+    mod_context!(CtxUnset);
+
+    #[test]
+    fn test_matches_modint() {
+        Ctx17::set_modulus(17);
+        Ctx17::set_modulus(17);
+        type D = DynModInt<Ctx17>;
+        type M = ModInt<17>;
+        for a in 0..17 {
+            for b in 0..17 {
+                let (da, db) = (D::new(a), D::new(b));
+                let (ma, mb) = (M::new(a), M::new(b));
+                assert_eq!((da + db).val(), (ma + mb).val());
+                assert_eq!((da - db).val(), (ma - mb).val());
+                assert_eq!((da * db).val(), (ma * mb).val());
+                if b != 0 {
+                    assert_eq!((da / db).val(), (ma / mb).val());
+                }
+            }
+        }
+        assert_eq!(D::from(-3i32), D::new(14));
+        assert_eq!(D::new(14).to_signed(), -3);
+        assert_eq!(i64::from(D::new(14)), -3);
+        assert_eq!(i8::try_from(D::new(14)), Ok(-3));
+        assert_eq!(D::from_bytes(&D::new(9).to_bytes()), Some(D::new(9)));
+        assert_eq!(D::from_bytes(&[17]), None);
+
+        let ids: Vec<D> = (1..=3u16).map(D::from).collect();
+        let expected: Vec<usize> = lagrange_coefficients(&[M::new(1), M::new(2), M::new(3)])
+            .iter()
+            .map(|l| l.val())
+            .collect();
+        let actual: Vec<usize> = lagrange_coefficients(&ids)
+            .iter()
+            .map(|l| l.val())
+            .collect();
+        assert_eq!(actual, expected);
+
+        let two = D::new(2);
+        assert_eq!(Pow::pow(two, 4usize), D::new(16));
+        assert_eq!(Pow::pow(two, 4u32), D::new(16));
+        assert_eq!(Pow::pow(two, D::new(4)), D::new(16));
+        assert_eq!(Inv::inv(two) * two, D::one());
+        assert_eq!(D::max_value() + D::one(), D::min_value());
+        assert_eq!(D::max_value().checked_add(&two), Some(D::new(1)));
+        assert_eq!(D::zero().checked_sub(&two), Some(-two));
+        assert_eq!(two.checked_mul(&two), Some(D::new(4)));
+        assert_eq!(two.checked_div(&D::zero()), None);
+
+        assert_eq!(D::from_i64(-1), Some(D::max_value()));
+        assert_eq!(D::from_f64(-2.0), Some(-two));
+        assert_eq!(D::from_f64(3.7), None);
+        assert_eq!(D::from_f32(f32::NAN), None);
+        assert_eq!(D::max_value().to_i64(), Some(-1));
+        assert_eq!(D::max_value().to_i128(), Some(-1));
+        assert_eq!(D::max_value().to_u8(), Some(16));
+        assert_eq!("16".parse::<D>(), Ok(D::new(16)));
+        assert_eq!("17".parse::<D>(), Err(ParseModIntError::OutOfRange));
+        assert!(matches!("x".parse::<D>(), Err(ParseModIntError::Int(_))));
+
+        assert_eq!(CtxUnset::try_modulus(), None);
+    }
+
+    #[test]
+    fn test_runtime_modulus() {
+        let m: usize = "18446744073709551557".parse().unwrap(); // 2^64 - 59
+        CtxBig::set_modulus(m);
+        type D = DynModInt<CtxBig>;
+        let max = D::new(m - 1);
+        assert!((max * max).is_one());
+        assert_eq!((max + max).val(), m - 2);
+        assert_eq!(D::new(3) / D::new(7) * D::new(7), D::new(3));
+        assert_eq!(D::characteristic(), m.to_be_bytes().to_vec());
+
+        let mut rng = rand::thread_rng();
+        let x = D::random(&mut rng);
+        assert!(x.val() < m);
+        let y = rng.gen_range(D::new(m - 10)..=max);
+        assert!(y.val() >= m - 10);
+        assert!(rng.gen_range(D::zero()..D::new(5)).val() < 5);
+        assert!(i32::try_from(D::new(1 << 40)).is_err());
+        assert!(std::panic::catch_unwind(|| CtxBig::set_modulus(17)).is_err());
+        assert!(std::panic::catch_unwind(|| CtxUnset::set_modulus(15)).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use num_traits::NumAssign;
+use rand::Rng;
+use std::convert::From;
+use std::fmt;
+use std::ops;
+
+// シェアの計算に使う有限体
+pub trait Field:
+    NumAssign + ops::Neg<Output = Self> + From<u16> + fmt::Display + fmt::Debug + Clone + Copy + 'static
+{
+    // 0の逆元はNone
+    fn checked_inv(&self) -> Option<Self>;
+
+    fn inv(&self) -> Self {
+        match self.checked_inv() {
+            Some(res) => res,
+            None => panic!("{} has no inverse.", self),
+        }
+    }
+
+    // 標数のビッグエンディアン表現 (先頭の0は除く)
+    fn characteristic() -> Vec<u8>;
+
+    // 体全体からの一様な元
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
+
+    fn random_vec<R: Rng + ?Sized>(rng: &mut R, n: usize) -> Vec<Self> {
+        (0..n).map(|_| Self::random(rng)).collect()
+    }
+
+    // 型ごとに固定長のビッグエンディアン表現
+    fn to_bytes(&self) -> Vec<u8>;
+
+    // 長さが違う、または正規形でないバイト列はNone
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+// 体なので割り切れない余りは存在しない。0 以外で割った余りは常に0
+macro_rules! impl_field_rem {
+    ([$($gen:tt)*] $ty:ty) => {
+        impl<$($gen)*> std::ops::Rem for $ty {
+            type Output = Self;
+
+            fn rem(self, other: Self) -> Self {
+                if num_traits::Zero::is_zero(&other) {
+                    panic!("0 division occured.");
+                }
+
+                <Self as num_traits::Zero>::zero()
+            }
+        }
+
+        impl<$($gen)*> std::ops::RemAssign for $ty {
+            fn rem_assign(&mut self, other: Self) {
+                *self = *self % other;
+            }
+        }
+    };
+}
+
+pub(crate) use impl_field_rem;
+
+// 先頭の0を取り除いたビッグエンディアン表現
+pub(crate) fn trim_be_bytes(bytes: &[u8]) -> Vec<u8> {
+    let start = bytes
+        .iter()
+        .position(|&b| b != 0)
+        .unwrap_or(bytes.len() - 1);
+    bytes[start..].to_vec()
+}
+
+// m - 1 を表すのに必要なバイト数 (最低1)
+pub(crate) const fn byte_len(m: u128) -> usize {
+    let bits = 128 - (m - 1).leading_zeros() as usize;
+    if bits == 0 {
+        1
+    } else {
+        bits.div_ceil(8)
+    }
+}
+
+pub(crate) fn to_be_fixed(val: u128, len: usize) -> Vec<u8> {
+    val.to_be_bytes()[16 - len..].to_vec()
+}
+
+pub(crate) fn from_be_fixed(bytes: &[u8], len: usize) -> Option<u128> {
+    if bytes.len() != len {
+        return None;
+    }
+    Some(bytes.iter().fold(0, |acc, &b| (acc << 8) | b as u128))
+}
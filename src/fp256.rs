@@ -0,0 +1,532 @@
+use crate::{field, Field};
+use num_traits::{
+    identities::{One, Zero},
+    Num,
+};
+use rand::Rng;
+use std::convert::{From, Into};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops;
+
+// 256bit素体の法。limbsはリトルエンディアンで、奇素数であること
+pub trait Fp256Params: Copy + Clone + fmt::Debug + PartialEq + Eq + 'static {
+    const MODULUS: [u64; 4];
+}
+
+// 2^127 - 1
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Mersenne127;
+
+impl Fp256Params for Mersenne127 {
+    const MODULUS: [u64; 4] = [0xffff_ffff_ffff_ffff, 0x7fff_ffff_ffff_ffff, 0, 0];
+}
+
+// 2^255 - 19
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Curve25519;
+
+impl Fp256Params for Curve25519 {
+    const MODULUS: [u64; 4] = [
+        0xffff_ffff_ffff_ffed,
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+        0x7fff_ffff_ffff_ffff,
+    ];
+}
+
+// NIST P-256: 2^256 - 2^224 + 2^192 + 2^96 - 1
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct P256;
+
+impl Fp256Params for P256 {
+    const MODULUS: [u64; 4] = [
+        0xffff_ffff_ffff_ffff,
+        0x0000_0000_ffff_ffff,
+        0x0000_0000_0000_0000,
+        0xffff_ffff_0000_0001,
+    ];
+}
+
+const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+// a + b * c + carry
+const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 * c as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+const fn geq(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    let mut i = 4;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+const fn sub_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut res = [0; 4];
+    let mut borrow = 0;
+    let mut i = 0;
+    while i < 4 {
+        let (r, b) = sbb(a[i], b[i], borrow);
+        res[i] = r;
+        borrow = b;
+        i += 1;
+    }
+    (res, borrow)
+}
+
+const fn add_limbs_mod(a: &[u64; 4], b: &[u64; 4], m: &[u64; 4]) -> [u64; 4] {
+    let mut res = [0; 4];
+    let mut carry = 0;
+    let mut i = 0;
+    while i < 4 {
+        let (r, c) = adc(a[i], b[i], carry);
+        res[i] = r;
+        carry = c;
+        i += 1;
+    }
+    if carry != 0 || geq(&res, m) {
+        sub_limbs(&res, m).0
+    } else {
+        res
+    }
+}
+
+const fn sub_limbs_mod(a: &[u64; 4], b: &[u64; 4], m: &[u64; 4]) -> [u64; 4] {
+    let (res, borrow) = sub_limbs(a, b);
+    if borrow != 0 {
+        let mut fixed = [0; 4];
+        let mut carry = 0;
+        let mut i = 0;
+        while i < 4 {
+            let (r, c) = adc(res[i], m[i], carry);
+            fixed[i] = r;
+            carry = c;
+            i += 1;
+        }
+        fixed
+    } else {
+        res
+    }
+}
+
+// 2^bits mod m
+const fn pow2_mod(bits: usize, m: &[u64; 4]) -> [u64; 4] {
+    let mut res = [1, 0, 0, 0];
+    let mut i = 0;
+    while i < bits {
+        res = add_limbs_mod(&res, &res, m);
+        i += 1;
+    }
+    res
+}
+
+// 商, 余り
+fn div_small(a: &[u64; 4], d: u64) -> ([u64; 4], u64) {
+    let mut q = [0; 4];
+    let mut r: u128 = 0;
+    for i in (0..4).rev() {
+        let cur = (r << 64) | a[i] as u128;
+        q[i] = (cur / d as u128) as u64;
+        r = cur % d as u128;
+    }
+    (q, r as u64)
+}
+
+pub struct Fp256<P: Fp256Params> {
+    limbs: [u64; 4], // Montgomery表現 (R = 2^256)
+    _params: PhantomData<P>,
+}
+
+impl<P: Fp256Params> Fp256<P> {
+    // -MODULUS^{-1} mod 2^64
+    const N0: u64 = {
+        let m = P::MODULUS[0];
+        assert!(
+            m % 2 == 1 && (P::MODULUS[1] | P::MODULUS[2] | P::MODULUS[3]) != 0,
+            "Fp256 requires an odd modulus larger than 2^64"
+        );
+        let mut inv = m;
+        let mut i = 0;
+        while i < 5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(m.wrapping_mul(inv)));
+            i += 1;
+        }
+        inv.wrapping_neg()
+    };
+    const R1: [u64; 4] = pow2_mod(256, &P::MODULUS);
+    const R2: [u64; 4] = pow2_mod(512, &P::MODULUS);
+
+    const fn from_mont(limbs: [u64; 4]) -> Self {
+        Self {
+            limbs,
+            _params: PhantomData,
+        }
+    }
+
+    // CIOS法によるMontgomery乗算
+    fn mont_mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        let m = &P::MODULUS;
+        let mut t = [0u64; 6];
+        for &bi in b.iter() {
+            let mut c = 0;
+            for j in 0..4 {
+                let (r, nc) = mac(t[j], a[j], bi, c);
+                t[j] = r;
+                c = nc;
+            }
+            let (r, nc) = adc(t[4], c, 0);
+            t[4] = r;
+            t[5] = nc;
+
+            let k = t[0].wrapping_mul(Self::N0);
+            let (_, mut c) = mac(t[0], k, m[0], 0);
+            for j in 1..4 {
+                let (r, nc) = mac(t[j], k, m[j], c);
+                t[j - 1] = r;
+                c = nc;
+            }
+            let (r, nc) = adc(t[4], c, 0);
+            t[3] = r;
+            t[4] = t[5] + nc;
+        }
+
+        let res = [t[0], t[1], t[2], t[3]];
+        if t[4] != 0 || geq(&res, m) {
+            sub_limbs(&res, m).0
+        } else {
+            res
+        }
+    }
+
+    // 法未満であることが前提
+    fn from_limbs_unchecked(limbs: [u64; 4]) -> Self {
+        Self::from_mont(Self::mont_mul(&limbs, &Self::R2))
+    }
+
+    // 法は64bitより大きいので還元は不要
+    pub fn new(n: usize) -> Self {
+        Self::from_limbs_unchecked([n as u64, 0, 0, 0])
+    }
+
+    // リトルエンディアンのlimbsから。法以上ならNone
+    pub fn from_limbs(limbs: [u64; 4]) -> Option<Self> {
+        if geq(&limbs, &P::MODULUS) {
+            None
+        } else {
+            Some(Self::from_limbs_unchecked(limbs))
+        }
+    }
+
+    pub fn to_limbs(&self) -> [u64; 4] {
+        Self::mont_mul(&self.limbs, &[1, 0, 0, 0])
+    }
+
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let mut limbs = [0u64; 4];
+        for (i, chunk) in bytes.chunks(8).enumerate() {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(chunk);
+            limbs[3 - i] = u64::from_be_bytes(buf);
+        }
+        Self::from_limbs(limbs)
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let limbs = self.to_limbs();
+        let mut res = [0u8; 32];
+        for (i, chunk) in res.chunks_mut(8).enumerate() {
+            chunk.copy_from_slice(&limbs[3 - i].to_be_bytes());
+        }
+        res
+    }
+
+    // 指数はリトルエンディアンのlimbs
+    pub fn pow_limbs(&self, exp: &[u64; 4]) -> Self {
+        let mut res = Self::one();
+        for i in (0..256).rev() {
+            res *= res;
+            if (exp[i / 64] >> (i % 64)) & 1 == 1 {
+                res *= *self;
+            }
+        }
+        res
+    }
+
+    pub fn pow_u(&self, n: usize) -> Self {
+        self.pow_limbs(&[n as u64, 0, 0, 0])
+    }
+
+    pub fn inv(&self) -> Self {
+        let exp = sub_limbs(&P::MODULUS, &[2, 0, 0, 0]).0;
+        self.pow_limbs(&exp)
+    }
+}
+
+impl<P: Fp256Params> Field for Fp256<P> {
+    fn checked_inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(Fp256::inv(self))
+        }
+    }
+
+    fn characteristic() -> Vec<u8> {
+        let mut bytes = [0u8; 32];
+        for (i, chunk) in bytes.chunks_mut(8).enumerate() {
+            chunk.copy_from_slice(&P::MODULUS[3 - i].to_be_bytes());
+        }
+        field::trim_be_bytes(&bytes)
+    }
+
+    // 法のビット長に切り詰めた乱数を棄却サンプリングする
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        // 0でない最上位のリムで切り詰め、それより上のリムは0にする
+        let top = (0..4).rev().find(|&i| P::MODULUS[i] != 0).unwrap();
+        let top_mask = u64::MAX >> P::MODULUS[top].leading_zeros();
+        loop {
+            let mut limbs: [u64; 4] = rng.gen();
+            limbs[top] &= top_mask;
+            for limb in limbs[top + 1..].iter_mut() {
+                *limb = 0;
+            }
+            if let Some(res) = Self::from_limbs(limbs) {
+                return res;
+            }
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut buf = [0u8; 32];
+        if bytes.len() != 32 {
+            return None;
+        }
+        buf.copy_from_slice(bytes);
+        Self::from_be_bytes(&buf)
+    }
+}
+
+impl<P: Fp256Params> Clone for Fp256<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: Fp256Params> Copy for Fp256<P> {}
+
+impl<P: Fp256Params> PartialEq for Fp256<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.limbs == other.limbs
+    }
+}
+
+impl<P: Fp256Params> Eq for Fp256<P> {}
+
+impl<P: Fp256Params> Num for Fp256<P> {
+    type FromStrRadixErr = <usize as Num>::FromStrRadixErr;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        if str.is_empty() {
+            usize::from_str_radix(str, radix)?;
+        }
+        let base = Self::new(radix as usize);
+        let mut res = Self::zero();
+        for (i, c) in str.char_indices() {
+            let d = usize::from_str_radix(&str[i..i + c.len_utf8()], radix)?;
+            res = res * base + Self::new(d);
+        }
+        Ok(res)
+    }
+}
+
+impl<P: Fp256Params> fmt::Display for Fp256<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000; // 10^19
+        let mut limbs = self.to_limbs();
+        let mut chunks = Vec::new();
+        loop {
+            let (q, r) = div_small(&limbs, CHUNK);
+            chunks.push(r);
+            limbs = q;
+            if limbs == [0; 4] {
+                break;
+            }
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for c in chunks.iter().rev() {
+            write!(f, "{:019}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: Fp256Params> fmt::Debug for Fp256<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limbs = self.to_limbs();
+        write!(
+            f,
+            "Fp256 {{ val: 0x{:016x}{:016x}{:016x}{:016x} }}",
+            limbs[3], limbs[2], limbs[1], limbs[0]
+        )
+    }
+}
+
+impl<P: Fp256Params, I> From<I> for Fp256<P>
+where
+    I: Into<usize>,
+{
+    fn from(n: I) -> Self {
+        Self::new(n.into())
+    }
+}
+
+impl<P: Fp256Params> ops::Add for Fp256<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::from_mont(add_limbs_mod(&self.limbs, &other.limbs, &P::MODULUS))
+    }
+}
+
+impl<P: Fp256Params> ops::AddAssign for Fp256<P> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<P: Fp256Params> ops::Neg for Fp256<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::zero() - self
+    }
+}
+
+impl<P: Fp256Params> ops::Sub for Fp256<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::from_mont(sub_limbs_mod(&self.limbs, &other.limbs, &P::MODULUS))
+    }
+}
+
+impl<P: Fp256Params> ops::SubAssign for Fp256<P> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<P: Fp256Params> ops::Mul for Fp256<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::from_mont(Self::mont_mul(&self.limbs, &other.limbs))
+    }
+}
+
+impl<P: Fp256Params> ops::MulAssign for Fp256<P> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<P: Fp256Params> ops::Div for Fp256<P> {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other.limbs == [0; 4] {
+            panic!("0 division occured.");
+        }
+
+        self * other.inv()
+    }
+}
+
+impl<P: Fp256Params> ops::DivAssign for Fp256<P> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+field::impl_field_rem!([P: Fp256Params] Fp256<P>);
+
+impl<P: Fp256Params> Zero for Fp256<P> {
+    fn zero() -> Self {
+        Self::from_mont([0; 4])
+    }
+
+    fn is_zero(&self) -> bool {
+        self.limbs == [0; 4]
+    }
+}
+
+impl<P: Fp256Params> One for Fp256<P> {
+    fn one() -> Self {
+        Self::from_mont(Self::R1)
+    }
+
+    fn is_one(&self) -> bool {
+        self.limbs == Self::R1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    type F25519 = Fp256<Curve25519>;
+    type Fnist = Fp256<P256>;
+
+    #[test]
+    fn test_arith() {
+        let minus_one = F25519::zero() - F25519::one();
+        assert!((minus_one * minus_one).is_one());
+        assert_eq!(
+            minus_one.to_string(),
+            "57896044618658097711785492504343953926634992332820282019728792003956564819948"
+        );
+        assert_eq!((minus_one + F25519::new(20)).to_string(), "19");
+
+        let a =
+            Fnist::from_str_radix("123456789abcdef0123456789abcdef0123456789abcdef", 16).unwrap();
+        let b = Fnist::new(987654321);
+        assert_eq!(a / b * b, a);
+        assert_eq!(Fnist::new(12345).pow_u(3).to_string(), "1881365963625");
+    }
+
+    #[test]
+    fn test_bytes_and_reconstruction() {
+        let mut key = [0u8; 32];
+        for (i, b) in key.iter_mut().enumerate() {
+            *b = (i as u8).wrapping_mul(37).wrapping_add(11) & 0x7f;
+        }
+        let secret = F25519::from_be_bytes(&key).unwrap();
+        assert_eq!(secret.to_be_bytes(), key);
+        assert!(F25519::from_be_bytes(&[0xff; 32]).is_none());
+
+        // f(x) = secret + r x を x = 1, 2 で評価して x = 0 を復元する
+        let r = F25519::from_str_radix("31415926535897932384626433832795028841971", 10).unwrap();
+        let s1 = secret + r * F25519::from(1u16);
+        let s2 = secret + r * F25519::from(2u16);
+        let two = F25519::from(2u16);
+        let recovered = s1 * two - s2;
+        assert_eq!(recovered.to_be_bytes(), key);
+    }
+}
@@ -0,0 +1,518 @@
+use crate::{add_mod, field, mul_mod, pow_mod, sub_mod, Field, ModInt};
+use num_traits::{
+    identities::{One, Zero},
+    Num,
+};
+use rand::Rng;
+use std::convert::{From, Into};
+use std::fmt;
+use std::ops;
+
+// 以下、係数列はインデックスが次数に対応する ([c0, c1, ..., c_{K-1}])
+
+const fn degree<const K: usize>(a: &[usize; K]) -> Option<usize> {
+    let mut i = K;
+    while i > 0 {
+        i -= 1;
+        if a[i] != 0 {
+            return Some(i);
+        }
+    }
+    None
+}
+
+// a * x mod (x^K + f)
+const fn mul_x<const MOD: usize, const K: usize>(a: &[usize; K], f: &[usize; K]) -> [usize; K] {
+    let mut res = [0; K];
+    let top = a[K - 1];
+    let mut i = K;
+    while i > 0 {
+        i -= 1;
+        let shifted = if i == 0 { 0 } else { a[i - 1] };
+        res[i] = sub_mod(shifted, mul_mod(top, f[i], MOD), MOD);
+    }
+    res
+}
+
+// a * b mod (x^K + f)
+const fn mul_poly<const MOD: usize, const K: usize>(
+    a: &[usize; K],
+    b: &[usize; K],
+    f: &[usize; K],
+) -> [usize; K] {
+    let mut res = [0; K];
+    let mut cur = *b;
+    let mut i = 0;
+    while i < K {
+        let mut j = 0;
+        while j < K {
+            res[j] = add_mod(res[j], mul_mod(a[i], cur[j], MOD), MOD);
+            j += 1;
+        }
+        cur = mul_x::<MOD, K>(&cur, f);
+        i += 1;
+    }
+    res
+}
+
+// aの次数が0以上, bが非零のときの a mod b。lead_one が真なら a に x^K の項があるとみなす
+const fn rem_poly<const MOD: usize, const K: usize>(
+    a: &[usize; K],
+    lead_one: bool,
+    b: &[usize; K],
+) -> [usize; K] {
+    let mut res = *a;
+    let db = match degree(b) {
+        Some(d) => d,
+        None => panic!("division by zero polynomial"),
+    };
+    let inv_lead = pow_mod(b[db], MOD - 2, MOD);
+
+    if lead_one {
+        // x^K - x^(K-db) * b / b[db]
+        let mut j = 0;
+        while j < db {
+            let t = mul_mod(inv_lead, b[j], MOD);
+            res[j + K - db] = sub_mod(res[j + K - db], t, MOD);
+            j += 1;
+        }
+    }
+
+    let mut i = K;
+    while i > db {
+        i -= 1;
+        if res[i] != 0 {
+            let q = mul_mod(res[i], inv_lead, MOD);
+            let mut j = 0;
+            while j <= db {
+                res[i - db + j] = sub_mod(res[i - db + j], mul_mod(q, b[j], MOD), MOD);
+                j += 1;
+            }
+        }
+    }
+    res
+}
+
+// Ben-Orの既約性判定: 1 <= i <= K/2 で gcd(x^(p^i) - x, f) = 1
+const fn is_irreducible<const MOD: usize, const K: usize>(f: &[usize; K]) -> bool {
+    let mut x = [0; K];
+    if K == 1 {
+        return true;
+    }
+    x[1] = 1;
+
+    let mut h = x;
+    let mut i = 1;
+    while i <= K / 2 {
+        // h = h^p mod f
+        let mut base = h;
+        let mut res = [0; K];
+        res[0] = 1;
+        let mut e = MOD;
+        while e > 0 {
+            if e % 2 == 1 {
+                res = mul_poly::<MOD, K>(&res, &base, f);
+            }
+            base = mul_poly::<MOD, K>(&base, &base, f);
+            e /= 2;
+        }
+        h = res;
+
+        let mut g = h;
+        g[1] = sub_mod(g[1], 1, MOD);
+        if degree(&g).is_none() {
+            return false;
+        }
+        let mut r = rem_poly::<MOD, K>(f, true, &g);
+        while degree(&r).is_some() {
+            let t = rem_poly::<MOD, K>(&g, false, &r);
+            g = r;
+            r = t;
+        }
+        if !matches!(degree(&g), Some(0)) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// モニックな既約多項式 x^K + f。係数の最大値が小さいものから順に探す。
+// 低次の係数から辞書順に進めると x^3 + c が全て可約な場合などに MOD 通り近く試すことになる
+const fn find_irreducible<const MOD: usize, const K: usize>() -> [usize; K] {
+    let mut bound = 2; // 係数は 0..bound で、bound - 1 を含むものだけ新たに試す
+    while bound <= MOD {
+        let mut f = [0; K];
+        'candidates: loop {
+            let mut has_top = false;
+            let mut i = 0;
+            while i < K {
+                if f[i] == bound - 1 {
+                    has_top = true;
+                }
+                i += 1;
+            }
+            if f[0] != 0 && has_top && is_irreducible::<MOD, K>(&f) {
+                return f;
+            }
+
+            let mut i = 0;
+            loop {
+                f[i] += 1;
+                if f[i] < bound {
+                    break;
+                }
+                f[i] = 0;
+                i += 1;
+                if i == K {
+                    break 'candidates;
+                }
+            }
+        }
+        bound += 1;
+    }
+    panic!("no irreducible polynomial found")
+}
+
+// F_{MOD^K} = F_MOD[x] / (x^K + f(x))
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Fpk<const MOD: usize, const K: usize> {
+    coeffs: [ModInt<MOD>; K],
+}
+
+impl<const MOD: usize, const K: usize> Fpk<MOD, K> {
+    const MODULUS: [usize; K] = {
+        assert!(K >= 1, "Fpk requires K >= 1");
+        let () = ModInt::<MOD>::ASSERT_PRIME;
+        find_irreducible::<MOD, K>()
+    };
+
+    pub fn from_coeffs(coeffs: [ModInt<MOD>; K]) -> Self {
+        Self { coeffs }
+    }
+
+    pub fn coeffs(&self) -> [ModInt<MOD>; K] {
+        self.coeffs
+    }
+
+    // 既約多項式 x^K + f(x) の f の係数
+    pub fn modulus() -> [ModInt<MOD>; K] {
+        let mut res = [ModInt::zero(); K];
+        for (r, &c) in res.iter_mut().zip(Self::MODULUS.iter()) {
+            *r = ModInt::new(c);
+        }
+        res
+    }
+
+    // 係数をMOD進数の各桁とみなす。MOD^K未満のnは相異なる元に移る
+    pub fn new(mut n: usize) -> Self {
+        let mut coeffs = [ModInt::zero(); K];
+        for c in coeffs.iter_mut() {
+            *c = ModInt::new(n % MOD);
+            n /= MOD;
+        }
+        Self { coeffs }
+    }
+
+    pub fn pow_u(&self, mut n: usize) -> Self {
+        let mut val = *self;
+        let mut res = Self::one();
+        while n > 0 {
+            if n % 2 == 1 {
+                res *= val;
+            }
+            val *= val;
+            n /= 2;
+        }
+
+        res
+    }
+
+    // a^(p^K - 2) = a^(p - 2) * Π_{i=1}^{K-1} (a^(p^i))^(p - 1)
+    pub fn inv(&self) -> Self {
+        if self.is_zero() {
+            panic!("0 has no inverse.");
+        }
+        let mut frob = *self;
+        let mut res = self.pow_u(MOD - 2);
+        for _ in 1..K {
+            frob = frob.pow_u(MOD);
+            res *= frob.pow_u(MOD - 1);
+        }
+        res
+    }
+}
+
+impl<const MOD: usize, const K: usize> Field for Fpk<MOD, K> {
+    fn checked_inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(Fpk::inv(self))
+        }
+    }
+
+    fn characteristic() -> Vec<u8> {
+        ModInt::<MOD>::characteristic()
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut coeffs = [ModInt::zero(); K];
+        for c in coeffs.iter_mut() {
+            *c = ModInt::random(rng);
+        }
+        Self { coeffs }
+    }
+
+    // 係数を低次から順に並べる
+    fn to_bytes(&self) -> Vec<u8> {
+        self.coeffs.iter().flat_map(|c| c.to_bytes()).collect()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let len = ModInt::<MOD>::BYTE_LEN;
+        if bytes.len() != K * len {
+            return None;
+        }
+        let mut coeffs = [ModInt::zero(); K];
+        for (c, chunk) in coeffs.iter_mut().zip(bytes.chunks(len)) {
+            *c = ModInt::from_bytes(chunk)?;
+        }
+        Some(Self { coeffs })
+    }
+}
+
+impl<const MOD: usize, const K: usize> Num for Fpk<MOD, K> {
+    type FromStrRadixErr = <usize as Num>::FromStrRadixErr;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        let val = usize::from_str_radix(str, radix)?;
+        Ok(Self::new(val))
+    }
+}
+
+impl<const MOD: usize, const K: usize> fmt::Display for Fpk<MOD, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for (i, c) in self.coeffs.iter().enumerate().rev() {
+            if c.is_zero() {
+                continue;
+            }
+            if !first {
+                write!(f, " + ")?;
+            }
+            first = false;
+            match i {
+                0 => write!(f, "{}", c)?,
+                1 if c.is_one() => write!(f, "x")?,
+                1 => write!(f, "{}x", c)?,
+                _ if c.is_one() => write!(f, "x^{}", i)?,
+                _ => write!(f, "{}x^{}", c, i)?,
+            }
+        }
+        if first {
+            write!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+impl<const MOD: usize, const K: usize, I> From<I> for Fpk<MOD, K>
+where
+    I: Into<usize>,
+{
+    fn from(n: I) -> Self {
+        Self::new(n.into())
+    }
+}
+
+impl<const MOD: usize, const K: usize> From<ModInt<MOD>> for Fpk<MOD, K> {
+    fn from(n: ModInt<MOD>) -> Self {
+        let mut coeffs = [ModInt::zero(); K];
+        coeffs[0] = n;
+        Self { coeffs }
+    }
+}
+
+impl<const MOD: usize, const K: usize> ops::Add for Fpk<MOD, K> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<const MOD: usize, const K: usize> ops::AddAssign for Fpk<MOD, K> {
+    fn add_assign(&mut self, other: Self) {
+        for (a, &b) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            *a += b;
+        }
+    }
+}
+
+impl<const MOD: usize, const K: usize> ops::Neg for Fpk<MOD, K> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self {
+        for c in self.coeffs.iter_mut() {
+            *c = -*c;
+        }
+        self
+    }
+}
+
+impl<const MOD: usize, const K: usize> ops::Sub for Fpk<MOD, K> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl<const MOD: usize, const K: usize> ops::SubAssign for Fpk<MOD, K> {
+    fn sub_assign(&mut self, other: Self) {
+        for (a, &b) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            *a -= b;
+        }
+    }
+}
+
+impl<const MOD: usize, const K: usize> ops::Mul for Fpk<MOD, K> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let f = Self::modulus();
+        let mut res = [ModInt::zero(); K];
+        let mut cur = other.coeffs; // other * x^i mod f
+        for &a in self.coeffs.iter() {
+            for (r, &c) in res.iter_mut().zip(cur.iter()) {
+                *r += a * c;
+            }
+            let top = cur[K - 1];
+            for i in (1..K).rev() {
+                cur[i] = cur[i - 1] - top * f[i];
+            }
+            cur[0] = ModInt::zero() - top * f[0];
+        }
+        Self { coeffs: res }
+    }
+}
+
+impl<const MOD: usize, const K: usize> ops::MulAssign for Fpk<MOD, K> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<const MOD: usize, const K: usize> ops::Div for Fpk<MOD, K> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        if other.is_zero() {
+            panic!("0 division occured.");
+        }
+
+        self * other.inv()
+    }
+}
+
+impl<const MOD: usize, const K: usize> ops::DivAssign for Fpk<MOD, K> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+field::impl_field_rem!([const MOD: usize, const K: usize] Fpk<MOD, K>);
+
+impl<const MOD: usize, const K: usize> Zero for Fpk<MOD, K> {
+    fn zero() -> Self {
+        Self {
+            coeffs: [ModInt::zero(); K],
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|c| c.is_zero())
+    }
+}
+
+impl<const MOD: usize, const K: usize> One for Fpk<MOD, K> {
+    fn one() -> Self {
+        Self::from(ModInt::one())
+    }
+
+    fn is_one(&self) -> bool {
+        self.coeffs[0].is_one() && self.coeffs[1..].iter().all(|c| c.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_axioms() {
+        type F9 = Fpk<3, 2>;
+        // x^2 + 1 は F_3 上既約で、係数が最小の候補として最初に見つかる
+        assert_eq!(F9::modulus(), [ModInt::new(1), ModInt::new(0)]);
+
+        let elems: Vec<F9> = (0..9usize).map(F9::new).collect();
+        for &a in elems.iter() {
+            if !a.is_zero() {
+                assert!((a * a.inv()).is_one());
+                assert!(a.pow_u(8).is_one());
+            }
+            for &b in elems.iter() {
+                for &c in elems.iter() {
+                    assert_eq!(a * (b + c), a * b + a * c);
+                    assert_eq!((a * b) * c, a * (b * c));
+                }
+            }
+        }
+        assert_eq!(F9::new(5).to_string(), "x + 2");
+
+        // p ≡ 2 (mod 3) では x^3 + c が全て可約なので x の項を含む多項式が選ばれる
+        type F = Fpk<1_000_000_007, 3>;
+        assert_ne!(F::modulus()[1], ModInt::zero());
+        let a = F::new(123_456_789_012_345);
+        assert!((a * a.inv()).is_one());
+        let frob = |x: F| x.pow_u(1_000_000_007);
+        assert_eq!(frob(frob(frob(a))), a);
+        assert_eq!(F9::from_bytes(&F9::new(5).to_bytes()), Some(F9::new(5)));
+        assert_eq!(F9::from_bytes(&[]), None);
+        assert_eq!(F9::from_bytes(&[1, 2, 0, 1]), None);
+    }
+
+    #[test]
+    fn test_more_players_than_modulus() {
+        type F = Fpk<17, 3>;
+        let secret = F::new(1234);
+        let rands: Vec<F> = (0..19usize).map(|i| F::new(i * 389 + 7)).collect();
+        let f = |x: F| {
+            let mut res = secret;
+            let mut xn = x;
+            for &r in rands.iter() {
+                res += r * xn;
+                xn *= x;
+            }
+            res
+        };
+
+        // id 1..=20 の20人(> 16人)でx = 0 の値を復元する
+        let ids: Vec<F> = (1..=20u16).map(F::from).collect();
+        let mut recovered = F::zero();
+        for (i, &p) in ids.iter().enumerate() {
+            let mut l = F::one();
+            for (j, &q) in ids.iter().enumerate() {
+                if i != j {
+                    l *= (F::zero() - q) / (p - q);
+                }
+            }
+            recovered += l * f(p);
+        }
+        assert_eq!(recovered, secret);
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{field, Field};
+use num_traits::{
+    identities::{One, Zero},
+    Num,
+};
+use rand::Rng;
+use std::convert::{From, Into};
+use std::fmt;
+use std::ops;
+
+// AESと同じ既約多項式 x^8 + x^4 + x^3 + x + 1 と生成元 x + 1 を用いる
+const POLY: u16 = 0x11b;
+const GENERATOR: u8 = 0x03;
+
+const fn make_tables() -> ([u8; 512], [u8; 256]) {
+    let mut exp = [0u8; 512];
+    let mut log = [0u8; 256];
+    let mut x: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = x as u8;
+        exp[i + 255] = x as u8;
+        log[x as usize] = i as u8;
+
+        // x *= GENERATOR
+        let mut prod = 0u16;
+        let mut a = x;
+        let mut b = GENERATOR;
+        while b != 0 {
+            if b & 1 == 1 {
+                prod ^= a;
+            }
+            a <<= 1;
+            if a & 0x100 != 0 {
+                a ^= POLY;
+            }
+            b >>= 1;
+        }
+        x = prod;
+        i += 1;
+    }
+    (exp, log)
+}
+
+const TABLES: ([u8; 512], [u8; 256]) = make_tables();
+const EXP: [u8; 512] = TABLES.0;
+const LOG: [u8; 256] = TABLES.1;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Gf256 {
+    val: u8,
+}
+
+impl Gf256 {
+    pub const fn new(val: u8) -> Self {
+        Self { val }
+    }
+
+    pub fn val(&self) -> u8 {
+        self.val
+    }
+
+    pub fn pow_u(&self, n: usize) -> Self {
+        if n == 0 {
+            return Self::one();
+        }
+        if self.val == 0 {
+            return Self::zero();
+        }
+        let e = (LOG[self.val as usize] as usize * (n % 255)) % 255;
+        Self { val: EXP[e] }
+    }
+
+    pub fn pow(&self, other: Self) -> Self {
+        self.pow_u(other.val as usize)
+    }
+
+    pub fn inv(&self) -> Self {
+        if self.val == 0 {
+            panic!("0 has no inverse.");
+        }
+        Self {
+            val: EXP[255 - LOG[self.val as usize] as usize],
+        }
+    }
+}
+
+impl Field for Gf256 {
+    fn checked_inv(&self) -> Option<Self> {
+        if self.val == 0 {
+            None
+        } else {
+            Some(Gf256::inv(self))
+        }
+    }
+
+    fn characteristic() -> Vec<u8> {
+        vec![2]
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self { val: rng.gen() }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        vec![self.val]
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            &[val] => Some(Self { val }),
+            _ => None,
+        }
+    }
+}
+
+impl Num for Gf256 {
+    type FromStrRadixErr = <u8 as Num>::FromStrRadixErr;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        let val = u8::from_str_radix(str, radix)?;
+        Ok(Self { val })
+    }
+}
+
+impl fmt::Display for Gf256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+// 整数をGF(2)係数の多項式とみなしてPOLYで割った余り
+impl<I> From<I> for Gf256
+where
+    I: Into<u16>,
+{
+    fn from(n: I) -> Self {
+        let mut n: u16 = n.into();
+        for i in (8..16).rev() {
+            if n & (1 << i) != 0 {
+                n ^= POLY << (i - 8);
+            }
+        }
+        Self { val: n as u8 }
+    }
+}
+
+impl ops::Add for Gf256 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, other: Self) -> Self {
+        Self {
+            val: self.val ^ other.val,
+        }
+    }
+}
+
+impl ops::AddAssign for Gf256 {
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn add_assign(&mut self, other: Self) {
+        self.val ^= other.val;
+    }
+}
+
+// 標数2なので -a = a
+impl ops::Neg for Gf256 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl ops::Sub for Gf256 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Self) -> Self {
+        Self {
+            val: self.val ^ other.val,
+        }
+    }
+}
+
+impl ops::SubAssign for Gf256 {
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn sub_assign(&mut self, other: Self) {
+        self.val ^= other.val;
+    }
+}
+
+impl ops::Mul for Gf256 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        if self.val == 0 || other.val == 0 {
+            return Self::zero();
+        }
+        Self {
+            val: EXP[LOG[self.val as usize] as usize + LOG[other.val as usize] as usize],
+        }
+    }
+}
+
+impl ops::MulAssign for Gf256 {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl ops::Div for Gf256 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other.val == 0 {
+            panic!("0 division occured.");
+        }
+        if self.val == 0 {
+            return Self::zero();
+        }
+        Self {
+            val: EXP[LOG[self.val as usize] as usize + 255 - LOG[other.val as usize] as usize],
+        }
+    }
+}
+
+impl ops::DivAssign for Gf256 {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+field::impl_field_rem!([] Gf256);
+
+impl Zero for Gf256 {
+    fn zero() -> Self {
+        Self { val: 0 }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.val == 0
+    }
+}
+
+impl One for Gf256 {
+    fn one() -> Self {
+        Self { val: 1 }
+    }
+
+    fn is_one(&self) -> bool {
+        self.val == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aes_vectors() {
+        assert_eq!(Gf256::new(0x57) * Gf256::new(0x83), Gf256::new(0xc1));
+        assert_eq!(Gf256::new(0x57) * Gf256::new(0x13), Gf256::new(0xfe));
+        assert_eq!(Gf256::new(0x53).inv(), Gf256::new(0xca));
+        assert_eq!(Gf256::from(0x100u16), Gf256::new(0x1b));
+
+        for a in 1..=255u8 {
+            let a = Gf256::new(a);
+            assert!((a * a.inv()).is_one());
+            assert_eq!(a.pow_u(255), Gf256::one());
+            assert_eq!(a / a, Gf256::one());
+        }
+    }
+
+    #[test]
+    fn test_bytewise_reconstruction() {
+        let secret = b"shamir";
+        let coeffs = [0x3c, 0x99, 0x01, 0xfe, 0x42, 0x7a];
+        let (x2, x3) = (Gf256::from(2u8), Gf256::from(3u8));
+        // x = 2, 3 のシェアから x = 0 でのLagrange補間
+        let l2 = x3 / (x3 - x2);
+        let l3 = x2 / (x2 - x3);
+
+        let recovered: Vec<u8> = secret
+            .iter()
+            .zip(coeffs.iter())
+            .map(|(&s, &c)| {
+                let f = |x: Gf256| Gf256::new(s) + Gf256::new(c) * x;
+                (l2 * f(x2) + l3 * f(x3)).val()
+            })
+            .collect();
+        assert_eq!(&recovered, secret);
+    }
+}
@@ -0,0 +1,396 @@
+use crate::{field, Field};
+use num_traits::{
+    identities::{One, Zero},
+    Num,
+};
+use rand::Rng;
+use std::convert::{From, Into};
+use std::fmt;
+use std::ops;
+
+// GF(2^K) = GF(2)[x] / (x^K + POLY)。POLYは既約多項式から x^K の項を除いたもの (既約性はコンパイル時に検査する)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Gf2k<const K: u32, const POLY: u128> {
+    val: u128,
+}
+
+// x^64 + x^4 + x^3 + x + 1
+pub type Gf2_64 = Gf2k<64, 0x1b>;
+// x^128 + x^7 + x^2 + x + 1 (GCMと同じ多項式)
+pub type Gf2_128 = Gf2k<128, 0x87>;
+
+// 繰り上がりのない64bit乗算
+const fn clmul64(a: u64, b: u64) -> u128 {
+    let a = a as u128;
+    let mut res = 0;
+    let mut i = 0;
+    while i < 64 {
+        if (b >> i) & 1 == 1 {
+            res ^= a << i;
+        }
+        i += 1;
+    }
+    res
+}
+
+// 繰り上がりのない128bit乗算。(上位, 下位)
+const fn clmul128(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = ((a >> 64) as u64, a as u64);
+    let (b1, b0) = ((b >> 64) as u64, b as u64);
+    let lo = clmul64(a0, b0);
+    let hi = clmul64(a1, b1);
+    let mid = clmul64(a0 ^ a1, b0 ^ b1) ^ lo ^ hi; // Karatsuba
+    (hi ^ (mid >> 64), lo ^ (mid << 64))
+}
+
+const fn mask_of(k: u32) -> u128 {
+    if k == 128 {
+        u128::MAX
+    } else {
+        (1 << k) - 1
+    }
+}
+
+// x^k ≡ poly を使って次数k以上の項を折り畳む
+const fn reduce(mut hi: u128, mut lo: u128, k: u32, poly: u128, mask: u128) -> u128 {
+    loop {
+        let h = if k == 128 {
+            hi
+        } else {
+            (hi << (128 - k)) | (lo >> k)
+        };
+        if h == 0 {
+            return lo & mask;
+        }
+        let (ph, pl) = clmul128(h, poly);
+        hi = ph;
+        lo = pl ^ (lo & mask);
+    }
+}
+
+const fn poly_degree(a: u128) -> u32 {
+    127 - a.leading_zeros()
+}
+
+// a mod b (b ≠ 0)
+const fn poly_rem(mut a: u128, b: u128) -> u128 {
+    let db = poly_degree(b);
+    while a != 0 && poly_degree(a) >= db {
+        a ^= b << (poly_degree(a) - db);
+    }
+    a
+}
+
+// gcd(x^k + poly, g) が定数か (g ≠ 0, deg g < k)
+const fn coprime_to_modulus(k: u32, poly: u128, g: u128) -> bool {
+    // x^k mod g を1次ずつ求める (x^k は u128 に収まらないことがある)
+    let dg = poly_degree(g);
+    let mut r = poly_rem(1, g);
+    let mut i = 0;
+    while i < k {
+        r <<= 1;
+        if (r >> dg) & 1 == 1 {
+            r ^= g;
+        }
+        i += 1;
+    }
+    let (mut a, mut b) = (g, r ^ poly_rem(poly, g));
+    while b != 0 {
+        let t = poly_rem(a, b);
+        a = b;
+        b = t;
+    }
+    a == 1
+}
+
+const fn is_small_prime(n: u32) -> bool {
+    let mut d = 2;
+    while d * d <= n {
+        if n.is_multiple_of(d) {
+            return false;
+        }
+        d += 1;
+    }
+    n >= 2
+}
+
+// Rabinの既約性判定: x^(2^k) ≡ x (mod f) かつ、kの各素因数qで gcd(x^(2^(k/q)) - x, f) = 1
+const fn is_irreducible(k: u32, poly: u128) -> bool {
+    if k == 1 {
+        return true;
+    }
+    let mask = mask_of(k);
+    let mut h = 2; // x
+    let mut j = 1;
+    while j <= k {
+        let (hi, lo) = clmul128(h, h);
+        h = reduce(hi, lo, k, poly, mask);
+        if j < k && k.is_multiple_of(j) && is_small_prime(k / j) {
+            let g = h ^ 2;
+            if g == 0 || !coprime_to_modulus(k, poly, g) {
+                return false;
+            }
+        }
+        j += 1;
+    }
+    h == 2
+}
+
+impl<const K: u32, const POLY: u128> Gf2k<K, POLY> {
+    const MASK: u128 = {
+        assert!(K >= 1 && K <= 128, "Gf2k supports 1 <= K <= 128");
+        assert!(
+            K == 128 || POLY >> K == 0,
+            "POLY must not contain terms of degree >= K"
+        );
+        assert!(is_irreducible(K, POLY), "x^K + POLY must be irreducible");
+        mask_of(K)
+    };
+
+    fn reduce(hi: u128, lo: u128) -> u128 {
+        reduce(hi, lo, K, POLY, Self::MASK)
+    }
+
+    pub fn new(val: u128) -> Self {
+        Self {
+            val: Self::reduce(0, val),
+        }
+    }
+
+    pub fn val(&self) -> u128 {
+        self.val
+    }
+
+    pub fn pow_u(&self, mut n: u128) -> Self {
+        let mut val = *self;
+        let mut res = Self::one();
+        while n > 0 {
+            if n % 2 == 1 {
+                res *= val;
+            }
+            val *= val;
+            n /= 2;
+        }
+
+        res
+    }
+
+    pub fn pow(&self, other: Self) -> Self {
+        self.pow_u(other.val)
+    }
+
+    // a^(2^K - 2)
+    pub fn inv(&self) -> Self {
+        if self.val == 0 {
+            panic!("0 has no inverse.");
+        }
+        self.pow_u(Self::MASK - 1)
+    }
+}
+
+impl<const K: u32, const POLY: u128> Field for Gf2k<K, POLY> {
+    fn checked_inv(&self) -> Option<Self> {
+        if self.val == 0 {
+            None
+        } else {
+            Some(Gf2k::inv(self))
+        }
+    }
+
+    fn characteristic() -> Vec<u8> {
+        vec![2]
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            val: rng.gen::<u128>() & Self::MASK,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        field::to_be_fixed(self.val, (K as usize).div_ceil(8))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let val = field::from_be_fixed(bytes, (K as usize).div_ceil(8))?;
+        if val & !Self::MASK == 0 {
+            Some(Self { val })
+        } else {
+            None
+        }
+    }
+}
+
+impl<const K: u32, const POLY: u128> Num for Gf2k<K, POLY> {
+    type FromStrRadixErr = <u128 as Num>::FromStrRadixErr;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        let val = u128::from_str_radix(str, radix)?;
+        Ok(Self::new(val))
+    }
+}
+
+impl<const K: u32, const POLY: u128> fmt::Display for Gf2k<K, POLY> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+// 整数をGF(2)係数の多項式とみなして既約多項式で割った余り
+impl<const K: u32, const POLY: u128, I> From<I> for Gf2k<K, POLY>
+where
+    I: Into<u128>,
+{
+    fn from(n: I) -> Self {
+        Self::new(n.into())
+    }
+}
+
+impl<const K: u32, const POLY: u128> ops::Add for Gf2k<K, POLY> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, other: Self) -> Self {
+        Self {
+            val: self.val ^ other.val,
+        }
+    }
+}
+
+impl<const K: u32, const POLY: u128> ops::AddAssign for Gf2k<K, POLY> {
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn add_assign(&mut self, other: Self) {
+        self.val ^= other.val;
+    }
+}
+
+// 標数2なので -a = a
+impl<const K: u32, const POLY: u128> ops::Neg for Gf2k<K, POLY> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl<const K: u32, const POLY: u128> ops::Sub for Gf2k<K, POLY> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Self) -> Self {
+        Self {
+            val: self.val ^ other.val,
+        }
+    }
+}
+
+impl<const K: u32, const POLY: u128> ops::SubAssign for Gf2k<K, POLY> {
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn sub_assign(&mut self, other: Self) {
+        self.val ^= other.val;
+    }
+}
+
+impl<const K: u32, const POLY: u128> ops::Mul for Gf2k<K, POLY> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let (hi, lo) = clmul128(self.val, other.val);
+        Self {
+            val: Self::reduce(hi, lo),
+        }
+    }
+}
+
+impl<const K: u32, const POLY: u128> ops::MulAssign for Gf2k<K, POLY> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<const K: u32, const POLY: u128> ops::Div for Gf2k<K, POLY> {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other.val == 0 {
+            panic!("0 division occured.");
+        }
+
+        self * other.inv()
+    }
+}
+
+impl<const K: u32, const POLY: u128> ops::DivAssign for Gf2k<K, POLY> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+field::impl_field_rem!([const K: u32, const POLY: u128] Gf2k<K, POLY>);
+
+impl<const K: u32, const POLY: u128> Zero for Gf2k<K, POLY> {
+    fn zero() -> Self {
+        Self { val: 0 }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.val == 0
+    }
+}
+
+impl<const K: u32, const POLY: u128> One for Gf2k<K, POLY> {
+    fn one() -> Self {
+        Self { val: 1 }
+    }
+
+    fn is_one(&self) -> bool {
+        self.val == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Gf256;
+
+    #[test]
+    fn test_matches_gf256() {
+        type G8 = Gf2k<8, 0x1b>;
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                let prod = G8::from(a) * G8::from(b);
+                assert_eq!(prod.val() as u8, (Gf256::new(a) * Gf256::new(b)).val());
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_fields() {
+        assert!(is_irreducible(2, 0b11));
+        assert!(!is_irreducible(2, 0b01)); // (x + 1)^2
+        assert!(is_irreducible(4, 0b0011));
+        assert!(is_irreducible(4, 0b1111)); // (x^5 + 1) / (x + 1)
+        assert!(!is_irreducible(4, 0b0101)); // (x^2 + x + 1)^2
+        assert!(!is_irreducible(8, 0x1a)); // x で割り切れる
+        assert!(!is_irreducible(6, 0b001011)); // (x^3 + x + 1)(x^3 + x^2 + 1)
+        assert!(is_irreducible(8, 0x1b));
+        assert!(is_irreducible(64, 0x1b));
+        assert!(is_irreducible(128, 0x87));
+        assert!(!is_irreducible(128, 0x86));
+
+        let x = Gf2_128::new(2);
+        assert_eq!(x.pow_u(128), Gf2_128::new(0x87));
+        assert_eq!(x.pow_u(127) * x, Gf2_128::new(0x87));
+        assert_eq!(Gf2_64::new(2).pow_u(64), Gf2_64::new(0x1b));
+        assert_eq!(Gf2_64::new(1 << 64), Gf2_64::new(0x1b));
+
+        let a = Gf2_128::new(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210);
+        let b = Gf2_128::new(u128::MAX);
+        assert!((a * a.inv()).is_one());
+        assert_eq!(a * b / b, a);
+        assert_eq!((a + b) * a, a * a + b * a);
+
+        let c = Gf2_64::new(0xdead_beef_cafe_babe);
+        assert!((c * c.inv()).is_one());
+    }
+}
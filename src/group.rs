@@ -0,0 +1,202 @@
+use crate::ntt::prime_factors;
+use crate::{Field, ModInt};
+use num_traits::identities::{One, Zero};
+use rand::Rng;
+use std::fmt;
+use std::hash::Hash;
+use std::ops;
+
+impl<const MOD: usize> ModInt<MOD> {
+    // 乗法群 (Z/MOD)^* での位数。factors は MOD - 1 の相異なる素因数
+    pub fn order_with_factors(&self, factors: &[usize]) -> usize {
+        let () = Self::ASSERT_PRIME;
+        if self.is_zero() {
+            panic!("0 is not in the multiplicative group.");
+        }
+        let mut ord = MOD - 1;
+        for &q in factors {
+            assert!(
+                (MOD - 1).is_multiple_of(q),
+                "{} does not divide {}",
+                q,
+                MOD - 1
+            );
+            while ord.is_multiple_of(q) && self.pow_u(ord / q).is_one() {
+                ord /= q;
+            }
+        }
+        ord
+    }
+
+    pub fn order(&self) -> usize {
+        self.order_with_factors(&prime_factors(MOD - 1))
+    }
+
+    pub fn is_generator_with_factors(&self, factors: &[usize]) -> bool {
+        !self.is_zero() && self.order_with_factors(factors) == MOD - 1
+    }
+
+    // 一様に選んで生成元が出るまで引き直す (生成元の割合は φ(p-1)/(p-1))
+    pub fn random_generator<R: Rng + ?Sized>(rng: &mut R, factors: &[usize]) -> Self {
+        loop {
+            let g = Self::random(rng);
+            if g.is_generator_with_factors(factors) {
+                return g;
+            }
+        }
+    }
+}
+
+// 位数が素数の巡回群。スカラー倍 (冪乗) は位数を法とする体の元で行う
+pub trait CyclicGroup:
+    ops::Mul<Output = Self> + PartialEq + Eq + Hash + fmt::Debug + fmt::Display + Clone + Copy
+{
+    type Scalar: Field;
+
+    fn identity() -> Self;
+    fn generator() -> Self;
+    fn exp(&self, k: Self::Scalar) -> Self;
+    fn inverse(&self) -> Self;
+
+    fn is_identity(&self) -> bool {
+        *self == Self::identity()
+    }
+}
+
+// Z_P^* の位数Qの部分群 (Q | P - 1)。P = 2Q + 1 の安全素数なら平方剰余の群
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Schnorr<const P: usize, const Q: usize> {
+    val: ModInt<P>,
+}
+
+impl<const P: usize, const Q: usize> Schnorr<P, Q> {
+    const VALID: () = {
+        assert!(ModInt::<P>::IS_PRIME, "P must be prime");
+        assert!(ModInt::<Q>::IS_PRIME, "Q must be prime");
+        assert!((P - 1).is_multiple_of(Q), "Q must divide P - 1");
+    };
+
+    // x^Q = 1 を満たす元だけを受け付ける
+    pub fn new(x: ModInt<P>) -> Option<Self> {
+        let () = Self::VALID;
+        if !x.is_zero() && x.pow_u(Q).is_one() {
+            Some(Self { val: x })
+        } else {
+            None
+        }
+    }
+
+    pub fn val(&self) -> ModInt<P> {
+        self.val
+    }
+
+    // h^((P-1)/Q) ≠ 1 となる最小のhから作る
+    fn find_generator() -> ModInt<P> {
+        let () = Self::VALID;
+        let mut h = ModInt::<P>::new(2);
+        loop {
+            let g = h.pow_u((P - 1) / Q);
+            if !g.is_one() {
+                return g;
+            }
+            h += ModInt::one();
+        }
+    }
+}
+
+impl<const P: usize, const Q: usize> CyclicGroup for Schnorr<P, Q> {
+    type Scalar = ModInt<Q>;
+
+    fn identity() -> Self {
+        Self { val: ModInt::one() }
+    }
+
+    fn generator() -> Self {
+        Self {
+            val: Self::find_generator(),
+        }
+    }
+
+    fn exp(&self, k: ModInt<Q>) -> Self {
+        Self {
+            val: self.val.pow_u(k.val()),
+        }
+    }
+
+    fn inverse(&self) -> Self {
+        Self {
+            val: self.val.inv(),
+        }
+    }
+}
+
+impl<const P: usize, const Q: usize> ops::Mul for Schnorr<P, Q> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            val: self.val * other.val,
+        }
+    }
+}
+
+impl<const P: usize, const Q: usize> fmt::Display for Schnorr<P, Q> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{is_safe_prime, random_safe_prime, Polynomial};
+
+    #[test]
+    fn test_order() {
+        type M = ModInt<2039>; // 2 * 1019 + 1
+        let factors = [2, 1019];
+        assert_eq!(M::new(1).order(), 1);
+        assert_eq!(M::new(2038).order(), 2);
+        assert_eq!(M::new(4).order_with_factors(&factors), 1019);
+        assert_eq!(M::primitive_root().order(), 2038);
+
+        let mut rng = rand::thread_rng();
+        let g = M::random_generator(&mut rng, &factors);
+        assert!(g.is_generator_with_factors(&factors));
+        assert!(!M::new(4).is_generator_with_factors(&factors));
+
+        for bits in [3, 10, 20, 40] {
+            let p = random_safe_prime(&mut rng, bits);
+            assert!(is_safe_prime(p));
+            assert_eq!(usize::BITS - p.leading_zeros(), bits);
+        }
+        assert!(is_safe_prime(2039));
+        assert!(!is_safe_prime(2029));
+    }
+
+    #[test]
+    fn test_feldman_vss() {
+        type G = Schnorr<2039, 1019>;
+        type S = ModInt<1019>;
+        let g = G::generator();
+        assert!(!g.is_identity());
+        assert!(g.exp(S::new(1018)) * g == G::identity());
+        assert_eq!(g.inverse() * g, G::identity());
+        assert_eq!(G::new(ModInt::new(2038)), None); // 位数2
+
+        // 係数へのコミットメント C_j = g^(a_j) でシェア f(i) を検証する
+        let mut rng = rand::thread_rng();
+        let f = Polynomial::random(S::new(42), 2, &mut rng);
+        let commits: Vec<G> = f.coeffs().iter().map(|&a| g.exp(a)).collect();
+        for i in 1..=5u16 {
+            let x = S::from(i);
+            let share = f.eval(x);
+            let expected = commits
+                .iter()
+                .rev()
+                .fold(G::identity(), |acc, &c| acc.exp(x) * c);
+            assert_eq!(g.exp(share), expected);
+            assert_ne!(g.exp(share + S::one()), expected);
+        }
+    }
+}
@@ -1,64 +1,243 @@
 use num_traits::{
     identities::{One, Zero},
-    Num,
+    Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, Inv, Num, Pow,
+    ToPrimitive,
 };
-use std::convert::{From, Into};
+use rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformSampler};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+use std::convert::{From, TryFrom};
+use std::error;
 use std::fmt;
+use std::iter;
+use std::num::{ParseIntError, TryFromIntError};
 use std::ops;
+use std::str;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+mod crt;
+mod ct;
+mod dynmod;
+mod field;
+mod fp256;
+mod fpk;
+mod gf256;
+mod gf2k;
+mod group;
+mod montgomery;
+mod multipoint;
+mod ntt;
+mod poly;
+mod prime;
+mod rational;
+mod sqrt;
+pub use crt::{crt, crt_product, crt_split, Rns2, Rns3};
+pub use ct::CtModInt;
+pub use dynmod::{DynModInt, ModContext, UniformDynModInt};
+pub use field::Field;
+pub use fp256::{Curve25519, Fp256, Fp256Params, Mersenne127, P256};
+pub use fpk::Fpk;
+pub use gf256::Gf256;
+pub use gf2k::{Gf2_128, Gf2_64, Gf2k};
+pub use group::{CyclicGroup, Schnorr};
+pub use montgomery::MontInt;
+pub use multipoint::SubproductTree;
+pub use ntt::{evaluate_at_roots, interpolate_from_roots, intt, ntt};
+pub use poly::Polynomial;
+pub use prime::{
+    is_prime, is_safe_prime, preset_prime, random_prime, random_safe_prime, Goldilocks,
+    Ntt998244353, F127, F255, F31, F61, F64,
+};
+
+// MODが2^32を超えても桁あふれしないよう、積はu128で計算する
+const fn add_mod(a: usize, b: usize, m: usize) -> usize {
+    let (s, overflow) = a.overflowing_add(b);
+    if overflow || s >= m {
+        s.wrapping_sub(m)
+    } else {
+        s
+    }
+}
+
+const fn sub_mod(a: usize, b: usize, m: usize) -> usize {
+    if a < b {
+        a.wrapping_sub(b).wrapping_add(m)
+    } else {
+        a - b
+    }
+}
+
+const fn mul_mod(a: usize, b: usize, m: usize) -> usize {
+    ((a as u128 * b as u128) % m as u128) as usize
+}
+
+// 拡張ユークリッドの互除法による a^{-1} mod m。gcd(a, m) != 1 ならNone
+const fn inv_mod(a: usize, m: usize) -> Option<usize> {
+    let (mut a, mut b) = (a as i128, m as i128);
+    let (mut x, mut y) = (1i128, 0i128);
+    while b != 0 {
+        let q = a / b;
+        (a, b) = (b, a - q * b);
+        (x, y) = (y, x - q * y);
+    }
+    if a == 1 {
+        Some(x.rem_euclid(m as i128) as usize)
+    } else {
+        None
+    }
+}
+
+const fn pow_mod(mut a: usize, mut n: usize, m: usize) -> usize {
+    let mut res = 1 % m;
+    while n > 0 {
+        if n % 2 == 1 {
+            res = mul_mod(res, a, m);
+        }
+        a = mul_mod(a, a, m);
+        n /= 2;
+    }
+    res
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ModInt<const MOD: usize> {
     val: usize,
 }
 
 impl<const MOD: usize> Num for ModInt<MOD> {
-    type FromStrRadixErr = <usize as Num>::FromStrRadixErr;
+    type FromStrRadixErr = ParseModIntError;
 
+    // 0..MOD 以外の値は受け付けない
     fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
-        let val = usize::from_str_radix(str, radix)?;
-        Ok(Self { val })
+        let val = usize::from_str_radix(str, radix).map_err(ParseModIntError::Int)?;
+        if val < MOD {
+            Ok(Self { val })
+        } else {
+            Err(ParseModIntError::OutOfRange)
+        }
+    }
+}
+
+impl<const MOD: usize> str::FromStr for ModInt<MOD> {
+    type Err = ParseModIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseModIntError {
+    Int(ParseIntError),
+    OutOfRange,
+}
+
+impl fmt::Display for ParseModIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseModIntError::Int(e) => write!(f, "{}", e),
+            ParseModIntError::OutOfRange => write!(f, "value is not less than the modulus"),
+        }
     }
 }
 
+impl error::Error for ParseModIntError {}
+
 impl<const MOD: usize> fmt::Display for ModInt<MOD> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.val)
     }
 }
 
-impl<const MOD: usize, I> From<I> for ModInt<MOD>
-where
-    I: Into<usize>,
-{
-    fn from(n: I) -> Self {
-        Self {
-            val: n.into() % MOD,
-        }
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl<const MOD: usize> From<$t> for ModInt<MOD> {
+                fn from(n: $t) -> Self {
+                    Self {
+                        val: (n as u128 % MOD as u128) as usize,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+// 負数は MOD を足して正の代表元に移す
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl<const MOD: usize> From<$t> for ModInt<MOD> {
+                fn from(n: $t) -> Self {
+                    Self {
+                        val: (n as i128).rem_euclid(MOD as i128) as usize,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl<const MOD: usize> From<ModInt<MOD>> for i64 {
+    fn from(n: ModInt<MOD>) -> Self {
+        n.to_signed()
     }
 }
 
+// to_signed() の値が収まらなければエラー
+macro_rules! impl_try_into_signed {
+    ($($t:ty),*) => {
+        $(
+            impl<const MOD: usize> TryFrom<ModInt<MOD>> for $t {
+                type Error = TryFromIntError;
+
+                fn try_from(n: ModInt<MOD>) -> Result<Self, Self::Error> {
+                    <$t>::try_from(n.to_signed())
+                }
+            }
+        )*
+    };
+}
+
+impl_try_into_signed!(i8, i16, i32);
+
 impl<const MOD: usize> ModInt<MOD> {
+    const BYTE_LEN: usize = field::byte_len(MOD as u128);
+    pub const IS_PRIME: bool = is_prime(MOD);
+    // 体として使う箇所で参照し、MODが素数でなければコンパイルエラーにする
+    pub(crate) const ASSERT_PRIME: () = assert!(
+        Self::IS_PRIME,
+        "ModInt<MOD> is used as a field but MOD is not prime"
+    );
+
     pub fn new(n: usize) -> Self {
         Self { val: n % MOD }
     }
 
     pub fn val(&self) -> usize {
-        // 念のためMOD演算
-        self.val % MOD
+        self.val
     }
 
-    pub fn _set_val(&mut self, val: usize) {
-        self.val = val % MOD;
+    // (-MOD/2, MOD/2] に入る代表元
+    pub fn to_signed(&self) -> i64 {
+        if self.val > MOD / 2 {
+            (self.val as i128 - MOD as i128) as i64
+        } else {
+            self.val as i64
+        }
     }
 
     pub fn pow_u(&self, mut n: usize) -> Self {
         let mut val = self.val;
-        let mut res: usize = 1;
+        let mut res: usize = 1 % MOD;
         while n > 0 {
             if n % 2 == 1 {
-                res = (res * val) % MOD;
+                res = mul_mod(res, val, MOD);
             }
-            val = (val * val) % MOD;
+            val = mul_mod(val, val, MOD);
             n /= 2;
         }
 
@@ -70,7 +249,15 @@ impl<const MOD: usize> ModInt<MOD> {
     }
 
     pub fn inv(&self) -> Self {
-        self.pow_u(MOD - 2)
+        match self.checked_inv() {
+            Some(res) => res,
+            None => panic!("{} is not invertible modulo {}.", self.val, MOD),
+        }
+    }
+
+    // gcd(val, MOD) != 1 ならNone
+    pub fn checked_inv(&self) -> Option<Self> {
+        inv_mod(self.val, MOD).map(|val| Self { val })
     }
 }
 
@@ -79,16 +266,14 @@ impl<const MOD: usize> ops::Add for ModInt<MOD> {
 
     fn add(self, other: Self) -> Self {
         Self {
-            val: (self.val + other.val) % MOD,
+            val: add_mod(self.val, other.val, MOD),
         }
     }
 }
 
 impl<const MOD: usize> ops::AddAssign for ModInt<MOD> {
     fn add_assign(&mut self, other: Self) {
-        *self = Self {
-            val: (self.val + other.val) % MOD,
-        };
+        self.val = add_mod(self.val, other.val, MOD);
     }
 }
 
@@ -97,40 +282,40 @@ impl<const MOD: usize> ops::Mul for ModInt<MOD> {
 
     fn mul(self, other: Self) -> Self {
         Self {
-            val: (self.val * other.val) % MOD,
+            val: mul_mod(self.val, other.val, MOD),
         }
     }
 }
 
 impl<const MOD: usize> ops::MulAssign for ModInt<MOD> {
     fn mul_assign(&mut self, other: Self) {
-        *self = Self {
-            val: (self.val * other.val) % MOD,
-        };
+        self.val = mul_mod(self.val, other.val, MOD);
     }
 }
 
-impl<const MOD: usize> ops::Sub for ModInt<MOD> {
+impl<const MOD: usize> ops::Neg for ModInt<MOD> {
     type Output = Self;
 
-    fn sub(mut self, other: Self) -> Self {
-        if self.val < other.val {
-            self.val += MOD;
+    fn neg(self) -> Self {
+        Self {
+            val: sub_mod(0, self.val, MOD),
         }
+    }
+}
+
+impl<const MOD: usize> ops::Sub for ModInt<MOD> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
         Self {
-            val: self.val - other.val % MOD,
+            val: sub_mod(self.val, other.val, MOD),
         }
     }
 }
 
 impl<const MOD: usize> ops::SubAssign for ModInt<MOD> {
     fn sub_assign(&mut self, other: Self) {
-        if self.val < other.val {
-            self.val += MOD;
-        }
-        *self = Self {
-            val: (self.val - other.val) % MOD,
-        };
+        self.val = sub_mod(self.val, other.val, MOD);
     }
 }
 
@@ -156,21 +341,25 @@ impl<const MOD: usize> ops::DivAssign for ModInt<MOD> {
     }
 }
 
+// a % b = a - (a / b) * b なので割り算ができれば常に0
 impl<const MOD: usize> ops::Rem for ModInt<MOD> {
     type Output = Self;
 
     fn rem(self, other: Self) -> Self {
-        Self {
-            val: (self.val % other.val) % MOD, // 念のためMOD演算
+        if other.val == 0 {
+            panic!("0 division occured.");
+        }
+        if other.checked_inv().is_none() {
+            panic!("{} is not invertible modulo {}.", other.val, MOD);
         }
+
+        Self::zero()
     }
 }
 
 impl<const MOD: usize> ops::RemAssign for ModInt<MOD> {
     fn rem_assign(&mut self, other: Self) {
-        *self = Self {
-            val: (self.val % other.val) % MOD, // 念のためMOD演算
-        };
+        *self = *self % other;
     }
 }
 
@@ -190,61 +379,461 @@ impl<const MOD: usize> Zero for ModInt<MOD> {
 
 impl<const MOD: usize> One for ModInt<MOD> {
     fn one() -> Self {
-        Self { val: 1 }
+        Self { val: 1 % MOD }
     }
 
     fn is_one(&self) -> bool {
-        self.val == 1
+        self.val == 1 % MOD
     }
 
     fn set_one(&mut self) {
-        self.val = 1;
+        self.val = 1 % MOD;
+    }
+}
+
+impl<const MOD: usize> iter::Sum for ModInt<MOD> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a, const MOD: usize> iter::Sum<&'a ModInt<MOD>> for ModInt<MOD> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, &x| acc + x)
+    }
+}
+
+impl<const MOD: usize> iter::Product for ModInt<MOD> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a, const MOD: usize> iter::Product<&'a ModInt<MOD>> for ModInt<MOD> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, &x| acc * x)
+    }
+}
+
+impl<const MOD: usize> Pow<usize> for ModInt<MOD> {
+    type Output = Self;
+
+    fn pow(self, n: usize) -> Self {
+        self.pow_u(n)
+    }
+}
+
+impl<const MOD: usize> Pow<u32> for ModInt<MOD> {
+    type Output = Self;
+
+    fn pow(self, n: u32) -> Self {
+        self.pow_u(n as usize)
+    }
+}
+
+impl<const MOD: usize> Pow<ModInt<MOD>> for ModInt<MOD> {
+    type Output = Self;
+
+    fn pow(self, other: Self) -> Self {
+        self.pow_u(other.val)
+    }
+}
+
+impl<const MOD: usize> Inv for ModInt<MOD> {
+    type Output = Self;
+
+    fn inv(self) -> Self {
+        ModInt::inv(&self)
+    }
+}
+
+// 代表元 0..MOD の範囲
+impl<const MOD: usize> Bounded for ModInt<MOD> {
+    fn min_value() -> Self {
+        Self { val: 0 }
+    }
+
+    fn max_value() -> Self {
+        Self { val: MOD - 1 }
+    }
+}
+
+// 剰余演算なので加減乗算は溢れない。除算は法と互いに素でなければNone
+impl<const MOD: usize> CheckedAdd for ModInt<MOD> {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(*self + *other)
+    }
+}
+
+impl<const MOD: usize> CheckedSub for ModInt<MOD> {
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(*self - *other)
+    }
+}
+
+impl<const MOD: usize> CheckedMul for ModInt<MOD> {
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(*self * *other)
+    }
+}
+
+impl<const MOD: usize> CheckedDiv for ModInt<MOD> {
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.checked_inv().map(|inv| *self * inv)
+    }
+}
+
+impl<const MOD: usize> FromPrimitive for ModInt<MOD> {
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(Self::from(n))
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(Self::from(n))
+    }
+
+    fn from_i128(n: i128) -> Option<Self> {
+        Some(Self::from(n))
+    }
+
+    fn from_u128(n: u128) -> Option<Self> {
+        Some(Self::from(n))
+    }
+
+    // 整数でない値は体の元に対応しないのでNone
+    fn from_f64(n: f64) -> Option<Self> {
+        if n.fract() != 0.0 {
+            return None;
+        }
+        i128::from_f64(n).map(Self::from)
+    }
+
+    fn from_f32(n: f32) -> Option<Self> {
+        Self::from_f64(n as f64)
+    }
+}
+
+// 符号なしは代表元 0..MOD、符号付きは i64::from と同じく to_signed の値を変換する
+impl<const MOD: usize> ToPrimitive for ModInt<MOD> {
+    fn to_i64(&self) -> Option<i64> {
+        Some(self.to_signed())
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.val.to_u64()
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        Some(self.to_signed() as i128)
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        Some(self.val as u128)
+    }
+}
+
+impl<const MOD: usize> Field for ModInt<MOD> {
+    fn checked_inv(&self) -> Option<Self> {
+        let () = Self::ASSERT_PRIME;
+        ModInt::checked_inv(self)
+    }
+
+    fn characteristic() -> Vec<u8> {
+        let () = Self::ASSERT_PRIME;
+        field::trim_be_bytes(&(MOD as u128).to_be_bytes())
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let () = Self::ASSERT_PRIME;
+        rng.gen()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        field::to_be_fixed(self.val as u128, Self::BYTE_LEN)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let val = field::from_be_fixed(bytes, Self::BYTE_LEN)?;
+        if val < MOD as u128 {
+            Some(Self { val: val as usize })
+        } else {
+            None
+        }
+    }
+}
+
+// [0, bound) の一様乱数。bound未満の最小の2冪に収まるよう上位ビットを落とし、範囲外なら引き直す
+fn sample_below<R: Rng + ?Sized>(rng: &mut R, bound: usize) -> usize {
+    if bound == 1 {
+        return 0;
+    }
+    let mask = usize::MAX >> (bound - 1).leading_zeros();
+    loop {
+        let val = rng.gen::<usize>() & mask;
+        if val < bound {
+            return val;
+        }
     }
 }
 
+impl<const MOD: usize> Distribution<ModInt<MOD>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ModInt<MOD> {
+        ModInt {
+            val: sample_below(rng, MOD),
+        }
+    }
+}
+
+// 代表元 0..MOD の順序での区間からの一様分布
+#[derive(Debug, Copy, Clone)]
+pub struct UniformModInt<const MOD: usize> {
+    low: usize,
+    range: usize,
+}
+
+impl<const MOD: usize> UniformSampler for UniformModInt<MOD> {
+    type X = ModInt<MOD>;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high) = (low.borrow().val, high.borrow().val);
+        assert!(low < high, "UniformModInt::new called with `low >= high`");
+        Self {
+            low,
+            range: high - low,
+        }
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high) = (low.borrow().val, high.borrow().val);
+        assert!(
+            low <= high,
+            "UniformModInt::new_inclusive called with `low > high`"
+        );
+        Self {
+            low,
+            range: high - low + 1,
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        ModInt {
+            val: self.low + sample_below(rng, self.range),
+        }
+    }
+}
+
+impl<const MOD: usize> SampleUniform for ModInt<MOD> {
+    type Sampler = UniformModInt<MOD>;
+}
+
+// Montgomeryの一括逆元計算。1回の逆元計算と3(n - 1)回の乗算で全要素を逆元に置き換える
+pub fn batch_inv<T: Field>(xs: &mut [T]) {
+    if xs.is_empty() {
+        return;
+    }
+    let mut prefix = Vec::with_capacity(xs.len());
+    let mut acc = T::one();
+    for &x in xs.iter() {
+        prefix.push(acc);
+        acc *= x;
+    }
+
+    let mut inv = match acc.checked_inv() {
+        Some(inv) => inv,
+        None => panic!("0 division occured."),
+    };
+    for (x, &p) in xs.iter_mut().zip(prefix.iter()).rev() {
+        let next = inv * *x;
+        *x = inv * p;
+        inv = next;
+    }
+}
+
+// 0 における Lagrange 係数 λ_i = Π_{j≠i} x_j / (x_j - x_i)
+pub fn lagrange_coefficients<T: Field>(xs: &[T]) -> Vec<T> {
+    let n = xs.len();
+    let mut denoms = vec![T::one(); n];
+    for (i, &xi) in xs.iter().enumerate() {
+        for (j, &xj) in xs.iter().enumerate() {
+            if i != j {
+                denoms[i] *= xj - xi;
+            }
+        }
+        if denoms[i].is_zero() {
+            panic!("Invalid participants");
+        }
+    }
+    batch_inv(&mut denoms);
+
+    // Π_{j≠i} x_j は前後からの累積積で求める
+    let mut suffix = vec![T::one(); n + 1];
+    for i in (0..n).rev() {
+        suffix[i] = suffix[i + 1] * xs[i];
+    }
+    let mut prefix = T::one();
+    let mut res = Vec::with_capacity(n);
+    for i in 0..n {
+        res.push(prefix * suffix[i + 1] * denoms[i]);
+        prefix *= xs[i];
+    }
+    res
+}
+
+// 階乗・逆元・階乗の逆元の表。必要になった分だけ伸ばす (MOD - 1 まで)
+#[derive(Debug, Clone)]
 pub struct ModCom<const MOD: usize> {
     fac: Vec<usize>,
     finv: Vec<usize>,
+    inv: Vec<usize>,
 }
 
 impl<const MOD: usize> ModCom<MOD> {
     pub fn new(cap: usize) -> Self {
-        let mut fac = vec![0; cap];
-        let mut finv = vec![0; cap];
-        let mut inv = vec![0; cap];
-        fac[0] = 1;
-        fac[1] = 1;
-        finv[0] = 1;
-        finv[1] = 1;
-        inv[1] = 1;
-        for i in 2..cap {
-            fac[i] = fac[i - 1] * i % MOD;
-            inv[i] = MOD - inv[MOD % i] * (MOD / i) % MOD;
-            finv[i] = finv[i - 1] * inv[i] % MOD;
+        let () = ModInt::<MOD>::ASSERT_PRIME;
+        let mut res = Self {
+            fac: vec![1],
+            finv: vec![1],
+            inv: vec![0],
+        };
+        if cap > 0 {
+            res.grow(cap - 1);
         }
+        res
+    }
 
-        Self { fac, finv }
+    // 0..=min(n, MOD - 1) の表を用意する
+    fn grow(&mut self, n: usize) {
+        let len = self.fac.len();
+        if n < len || len == MOD {
+            return;
+        }
+        let new_len = (n + 1).max(2 * len).min(MOD);
+        for i in len..new_len {
+            let inv = if i == 1 {
+                1
+            } else {
+                MOD - mul_mod(self.inv[MOD % i], MOD / i, MOD)
+            };
+            self.inv.push(inv);
+            self.fac.push(mul_mod(self.fac[i - 1], i, MOD));
+            self.finv.push(mul_mod(self.finv[i - 1], inv, MOD));
+        }
     }
 
-    pub fn com(&self, n: usize, k: usize) -> usize {
+    pub fn fact(&mut self, n: usize) -> ModInt<MOD> {
+        if n >= MOD {
+            return ModInt::zero();
+        }
+        self.grow(n);
+        ModInt::new(self.fac[n])
+    }
+
+    // n < MOD の場合
+    fn com_small(&mut self, n: usize, k: usize) -> usize {
         if n < k {
             return 0;
         }
-        self.fac[n] * (self.finv[k] * self.finv[n - k] % MOD) % MOD
+        self.grow(n);
+        mul_mod(
+            self.fac[n],
+            mul_mod(self.finv[k], self.finv[n - k], MOD),
+            MOD,
+        )
+    }
+
+    // n >= MOD では Lucas の定理で MOD 進の各桁に分ける
+    pub fn com(&mut self, mut n: usize, mut k: usize) -> ModInt<MOD> {
+        if n < k {
+            return ModInt::zero();
+        }
+        let mut res = ModInt::one();
+        while k > 0 {
+            res *= ModInt::new(self.com_small(n % MOD, k % MOD));
+            if res.is_zero() {
+                break;
+            }
+            n /= MOD;
+            k /= MOD;
+        }
+        res
+    }
+
+    // n! / (n - k)!
+    pub fn perm(&mut self, n: usize, k: usize) -> ModInt<MOD> {
+        if n < k {
+            return ModInt::zero();
+        }
+        // n - k + 1..=n にMODの倍数があれば0
+        if n / MOD != (n - k) / MOD {
+            return ModInt::zero();
+        }
+        let (n, m) = (n % MOD, (n - k) % MOD);
+        self.grow(n);
+        ModInt::new(mul_mod(self.fac[n], self.finv[m], MOD))
+    }
+
+    // (k_1 + ... + k_r)! / (k_1! ... k_r!) = Π C(k_1 + ... + k_i, k_i)
+    pub fn multinomial(&mut self, ks: &[usize]) -> ModInt<MOD> {
+        let mut total = 0;
+        let mut res = ModInt::one();
+        for &k in ks {
+            total += k;
+            res *= self.com(total, k);
+        }
+        res
+    }
+
+    // 連続したid start..=end が参加するときの 0 における Lagrange 係数。
+    // s = start - 1, n = end - s として λ_i = (-1)^(i-1) (s+n)! / (s! (s+i) (i-1)! (n-i)!)
+    pub fn lagrange_coefficients(&mut self, ids: ops::RangeInclusive<usize>) -> Vec<ModInt<MOD>> {
+        let (start, end) = ids.into_inner();
+        if start > end {
+            return vec![];
+        }
+        assert!(start >= 1, "Invalid participants");
+        assert!(end < MOD, "Invalid participants");
+        self.grow(end);
+
+        let s = start - 1;
+        let n = end - s;
+        let whole = mul_mod(self.fac[s + n], self.finv[s], MOD);
+        (1..=n)
+            .map(|i| {
+                let inv_x = mul_mod(self.fac[s + i - 1], self.finv[s + i], MOD);
+                let v = mul_mod(
+                    mul_mod(whole, inv_x, MOD),
+                    mul_mod(self.finv[i - 1], self.finv[n - i], MOD),
+                    MOD,
+                );
+                if i % 2 == 1 {
+                    ModInt::new(v)
+                } else {
+                    -ModInt::new(v)
+                }
+            })
+            .collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    type MINT = ModInt<1_000_000_007>;
+    type Mint = ModInt<1_000_000_007>;
 
     #[test]
     fn test1() {
-        let a = MINT::new(111);
-        let b = MINT::new(222);
-        let c = MINT::new(333);
-        let d = MINT::new(444);
+        let a = Mint::new(111);
+        let b = Mint::new(222);
+        let c = Mint::new(333);
+        let d = Mint::new(444);
 
         let res = a * b + c - d;
         assert_eq!(res.val(), 24531);
@@ -252,12 +841,298 @@ mod tests {
 
     #[test]
     fn test2() {
-        let a = MINT::new(111111111);
-        let b = MINT::new(222222222);
-        let c = MINT::new(333333333);
-        let d = MINT::new(444444444);
+        let a = Mint::new(111111111);
+        let b = Mint::new(222222222);
+        let c = Mint::new(333333333);
+        let d = Mint::new(444444444);
 
         let res = a * b + c - d;
         assert_eq!(res.val(), 691358032);
     }
+
+    #[test]
+    fn test_mersenne61() {
+        type M61 = ModInt<2_305_843_009_213_693_951>; // 2^61 - 1
+        let a = M61::new(2_305_843_009_213_693_950); // -1
+        let b = M61::new(1 << 60);
+
+        assert_eq!((a * a).val(), 1);
+        assert_eq!((a * b).val(), (1 << 60) - 1);
+        assert_eq!((b + b).val(), 1);
+        assert_eq!((a * b.inv() * b).val(), a.val());
+    }
+
+    #[test]
+    fn test_u64_boundary() {
+        type M64 = ModInt<18_446_744_073_709_551_557>; // 2^64 - 59, 64bit最大の素数
+        let max = M64::new(18_446_744_073_709_551_556); // -1
+        let one = M64::new(1);
+
+        assert_eq!((max + max).val(), 18_446_744_073_709_551_555);
+        assert_eq!((max + one).val(), 0);
+        assert_eq!((one - max).val(), 2);
+        assert_eq!((max * max).val(), 1);
+        assert_eq!(max.pow_u(3).val(), max.val());
+        assert_eq!((M64::new(12345) / max * max).val(), 12345);
+
+        let mut c = max;
+        c += max;
+        c -= max;
+        c *= max;
+        assert_eq!(c.val(), 1);
+    }
+
+    #[test]
+    fn test_field() {
+        let a = Mint::new(123456789);
+        assert_eq!(Mint::characteristic(), vec![0x3b, 0x9a, 0xca, 0x07]);
+        assert_eq!(a.to_bytes(), vec![0x07, 0x5b, 0xcd, 0x15]);
+        assert_eq!(Mint::from_bytes(&a.to_bytes()), Some(a));
+        assert_eq!(Mint::from_bytes(&[0x3b, 0x9a, 0xca, 0x07]), None);
+        assert_eq!(Mint::from_bytes(&[0x07]), None);
+        assert!((Field::inv(&a) * a).is_one());
+        assert_eq!(Mint::zero().checked_inv(), None);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            assert!(Mint::random(&mut rng).val() < 1_000_000_007);
+        }
+    }
+
+    #[test]
+    fn test_lagrange_coefficients() {
+        let mut xs: Vec<Mint> = (1..=300usize).map(|i| Mint::new(i * i + 7)).collect();
+        let invs: Vec<Mint> = xs.iter().map(|x| x.inv()).collect();
+        batch_inv(&mut xs);
+        assert_eq!(xs, invs);
+
+        let ids: Vec<Mint> = (1..=200u16).map(Mint::from).collect();
+        let fast = lagrange_coefficients(&ids);
+        for (i, &p) in ids.iter().enumerate().step_by(37) {
+            let mut r = Mint::one();
+            for (j, &q) in ids.iter().enumerate() {
+                if i != j {
+                    r *= (Mint::zero() - q) / (p - q);
+                }
+            }
+            assert_eq!(fast[i], r);
+        }
+        assert!(fast.iter().fold(Mint::zero(), |acc, &l| acc + l).is_one());
+
+        let mut com = ModCom::<1_000_000_007>::new(0);
+        assert_eq!(com.lagrange_coefficients(1..=200), fast);
+        let ids: Vec<Mint> = (57..=300u16).map(Mint::from).collect();
+        assert_eq!(
+            com.lagrange_coefficients(57..=300),
+            lagrange_coefficients(&ids)
+        );
+        let ids = [Mint::new(4)];
+        assert_eq!(
+            com.lagrange_coefficients(4..=4),
+            lagrange_coefficients(&ids)
+        );
+    }
+
+    #[test]
+    fn test_modcom() {
+        let mut com = ModCom::<1_000_000_007>::new(0);
+        assert_eq!(com.com(5, 2), Mint::new(10));
+        assert_eq!(com.com(2, 5), Mint::zero());
+        assert_eq!(com.com(1000, 500), Mint::new(159_835_829));
+        assert_eq!(com.perm(10, 3), Mint::new(720));
+        assert_eq!(com.multinomial(&[2, 3, 4]), Mint::new(1260));
+        assert_eq!(com.fact(20), Mint::new(146_326_063));
+
+        // Lucas: C(n, k) mod 7
+        let mut com7 = ModCom::<7>::new(1);
+        let mut pascal = vec![vec![1usize]];
+        for n in 1..60 {
+            let prev = &pascal[n - 1];
+            let row: Vec<usize> = (0..=n)
+                .map(|k| {
+                    let a = if k > 0 { prev[k - 1] } else { 0 };
+                    let b = if k < n { prev[k] } else { 0 };
+                    (a + b) % 7
+                })
+                .collect();
+            pascal.push(row);
+        }
+        for (n, row) in pascal.iter().enumerate() {
+            for (k, &c) in row.iter().enumerate() {
+                assert_eq!(com7.com(n, k), ModInt::new(c));
+            }
+        }
+        assert_eq!(com7.perm(9, 2), ModInt::new(72));
+        assert_eq!(com7.perm(9, 3), ModInt::zero());
+        assert_eq!(com7.fact(7), ModInt::zero());
+    }
+
+    #[test]
+    fn test_num_traits() {
+        let xs: Vec<Mint> = (1..=10usize).map(Mint::new).collect();
+        assert_eq!(xs.iter().sum::<Mint>(), Mint::new(55));
+        assert_eq!(xs.iter().copied().sum::<Mint>(), Mint::new(55));
+        assert_eq!(xs.iter().product::<Mint>(), Mint::new(3_628_800));
+        assert_eq!(xs.into_iter().product::<Mint>(), Mint::new(3_628_800));
+        assert_eq!(std::iter::empty::<Mint>().product::<Mint>(), Mint::one());
+
+        let two = Mint::new(2);
+        assert_eq!(Pow::pow(two, 10usize), Mint::new(1024));
+        assert_eq!(Pow::pow(two, 10u32), Mint::new(1024));
+        assert_eq!(Pow::pow(two, Mint::new(10)), Mint::new(1024));
+        assert_eq!(Inv::inv(two) * two, Mint::one());
+        assert_eq!(Mint::max_value() + Mint::one(), Mint::min_value());
+
+        assert_eq!(Mint::max_value().checked_add(&two), Some(Mint::new(1)));
+        assert_eq!(Mint::zero().checked_sub(&two), Some(-two));
+        assert_eq!(two.checked_mul(&two), Some(Mint::new(4)));
+        assert_eq!(two.checked_div(&Mint::zero()), None);
+        assert_eq!(ModInt::<15>::new(1).checked_div(&ModInt::new(5)), None);
+        assert_eq!(
+            ModInt::<15>::new(1).checked_div(&ModInt::new(7)),
+            Some(ModInt::new(13))
+        );
+
+        assert_eq!(Mint::from_i64(-1), Some(Mint::max_value()));
+        assert_eq!(Mint::from_f64(3.0), Some(Mint::new(3)));
+        assert_eq!(Mint::from_f64(-2.0), Some(-two));
+        assert_eq!(Mint::from_f64(3.7), None);
+        assert_eq!(Mint::from_f64(f64::NAN), None);
+        assert_eq!(Mint::from_f32(f32::INFINITY), None);
+        assert_eq!(Mint::max_value().to_u32(), Some(1_000_000_006));
+        assert_eq!(Mint::max_value().to_u16(), None);
+        assert_eq!(
+            Mint::max_value().to_i64(),
+            Some(i64::from(Mint::max_value()))
+        );
+        assert_eq!(Mint::max_value().to_i8(), Some(-1));
+        assert_eq!(ModInt::<17>::new(16).to_i128(), Some(-1));
+        assert_eq!(
+            ModInt::<18_446_744_073_709_551_557>::new(1 << 63).to_i64(),
+            Some(i64::MIN + 59)
+        );
+
+        let set: std::collections::HashSet<Mint> = [1usize, 2, 1_000_000_008]
+            .iter()
+            .map(|&x| Mint::new(x))
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_canonical() {
+        assert_eq!(
+            Mint::from_str_radix("1000000006", 10),
+            Ok(Mint::new(1_000_000_006))
+        );
+        assert_eq!(
+            Mint::from_str_radix("1000000007", 10),
+            Err(ParseModIntError::OutOfRange)
+        );
+        assert!(matches!(
+            "-1".parse::<Mint>(),
+            Err(ParseModIntError::Int(_))
+        ));
+        assert!("ff".parse::<Mint>().is_err());
+        assert_eq!(Mint::from_str_radix("ff", 16), Ok(Mint::new(255)));
+
+        let a: Mint = "42".parse().unwrap();
+        let b = Mint::new(1_000_000_049);
+        assert_eq!(a, b);
+        let set: std::collections::HashSet<Mint> = [a, b].iter().copied().collect();
+        assert_eq!(set.len(), 1);
+
+        assert_eq!(Mint::new(10) % Mint::new(3), Mint::zero());
+        let mut c = Mint::new(10);
+        c %= Mint::new(7);
+        assert!(c.is_zero());
+        assert!(ModInt::<1>::one().is_zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "0 division occured.")]
+    fn test_rem_by_zero() {
+        let _ = Mint::new(10) % Mint::zero();
+    }
+
+    #[test]
+    fn test_signed() {
+        type M17 = ModInt<17>;
+        assert_eq!(-M17::new(3), M17::new(14));
+        assert_eq!(-M17::zero(), M17::zero());
+        assert_eq!(M17::from(-3i32), M17::new(14));
+        assert_eq!(M17::from(-35i64), M17::new(16));
+        assert_eq!(M17::new(8).to_signed(), 8);
+        assert_eq!(M17::new(9).to_signed(), -8);
+        assert_eq!(ModInt::<16>::new(8).to_signed(), 8);
+        assert_eq!(ModInt::<16>::new(9).to_signed(), -7);
+        assert_eq!(i8::try_from(M17::new(16)), Ok(-1));
+        assert!(i8::try_from(Mint::new(1000)).is_err());
+
+        type M64 = ModInt<18_446_744_073_709_551_557>;
+        assert_eq!(M64::from(i64::MIN + 30).to_signed(), i64::MIN + 30);
+        assert_eq!(M64::from(i64::MAX - 30).to_signed(), i64::MAX - 30);
+        assert_eq!(M64::from(i64::MIN).to_signed(), i64::MAX - 58);
+
+        // 負の値をシェアしても符号付きで復元できる
+        let secret = Mint::from(-1_234_567i64);
+        let r = Mint::new(987_654);
+        let (s1, s2, s3) = (
+            secret + r,
+            secret + r * Mint::new(2),
+            secret + r * Mint::new(3),
+        );
+        let ls = lagrange_coefficients(&[Mint::new(1), Mint::new(2), Mint::new(3)]);
+        let recovered = ls[0] * s1 + ls[1] * s2 + ls[2] * s3;
+        assert_eq!(i64::from(recovered), -1_234_567);
+    }
+
+    #[test]
+    fn test_composite_modulus() {
+        type M15 = ModInt<15>;
+        let primality = [
+            M15::IS_PRIME,
+            Mint::IS_PRIME,
+            ModInt::<18_446_744_073_709_551_557>::IS_PRIME,
+            ModInt::<{ 4_294_967_291 * 4_294_967_279 }>::IS_PRIME,
+        ];
+        assert_eq!(primality, [false, true, true, false]);
+
+        assert_eq!(M15::new(2).checked_inv(), Some(M15::new(8)));
+        assert_eq!(M15::new(3).checked_inv(), None);
+        assert_eq!(M15::new(0).checked_inv(), None);
+        assert_eq!(M15::new(7) / M15::new(7), M15::new(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "5 is not invertible modulo 15.")]
+    fn test_non_invertible_division() {
+        let _ = ModInt::<15>::new(1) / ModInt::<15>::new(5);
+    }
+
+    #[test]
+    fn test_random() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        type M17 = ModInt<17>;
+        let mut rng = StdRng::seed_from_u64(17);
+
+        let mut counts = [0usize; 17];
+        for x in M17::random_vec(&mut rng, 17_000) {
+            counts[x.val()] += 1;
+        }
+        // 期待値1000, 標準偏差約31
+        assert!(counts.iter().all(|&c| 850 < c && c < 1150), "{:?}", counts);
+
+        for _ in 0..1000 {
+            let x = rng.gen_range(M17::new(3)..M17::new(5));
+            assert!(x == M17::new(3) || x == M17::new(4));
+            let y = rng.gen_range(M17::new(15)..=M17::new(16));
+            assert!(y.val() >= 15);
+        }
+
+        type M64 = ModInt<18_446_744_073_709_551_557>;
+        let z: M64 = rng.gen();
+        assert!(z.val() < 18_446_744_073_709_551_557);
+    }
 }
@@ -1,23 +1,23 @@
-use num_traits::{NumAssign, One, Zero};
+use num_traits::Zero;
+use shamir_share::{lagrange_coefficients, Field, Polynomial};
 use std::collections::HashMap;
-use std::convert::From;
 use std::fmt;
 
 struct Player<T>
 where
-    T: NumAssign + From<u16> + fmt::Display + fmt::Debug + Clone + Copy + 'static,
+    T: Field,
 {
     id: u16,
     secret: T,
     rands: Vec<T>,
-    poly: Option<Box<dyn Fn(T) -> T>>,
+    poly: Option<Polynomial<T>>,
     shares: HashMap<u16, T>,
     folded_share: T,
 }
 
 impl<T> fmt::Debug for Player<T>
 where
-    T: NumAssign + From<u16> + fmt::Display + fmt::Debug + Clone + Copy + 'static,
+    T: Field,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -30,7 +30,7 @@ where
 
 impl<T> Player<T>
 where
-    T: NumAssign + From<u16> + fmt::Display + fmt::Debug + Clone + Copy + 'static,
+    T: Field,
 {
     fn new(id: u16, secret: T, rands: Vec<T>) -> Self {
         Self {
@@ -44,23 +44,28 @@ where
     }
 
     fn make_poly(&mut self, k: usize) {
-        let rands = self.rands.clone();
-        let secret = self.secret;
-        self.poly = Some(Box::new(move |x: T| {
-            let mut res = secret;
-            let mut xn = x;
-            for i in 0..(k - 1) {
-                res += rands[i] * xn;
-                xn *= x;
-            }
-            res
-        }));
-        self.shares
-            .insert(self.id, self.poly.as_ref().unwrap()(self.id.into()));
+        // 乱数が足りないと次数が下がり、k人未満で復元できてしまう
+        assert!(
+            self.rands.len() >= k - 1,
+            "{} random coefficients are needed for threshold {}, but only {} given",
+            k - 1,
+            k,
+            self.rands.len()
+        );
+        let mut coeffs = vec![self.secret];
+        coeffs.extend(self.rands.iter().take(k - 1));
+        self.poly = Some(Polynomial::new(coeffs));
+        self.shares.insert(self.id, self.give_share(self.id));
     }
 
     fn give_share(&self, opposite_id: u16) -> T {
-        self.poly.as_ref().unwrap()(opposite_id.into())
+        self.poly.as_ref().unwrap().eval(opposite_id.into())
+    }
+
+    // 多数の相手へのシェアをまとめて計算する
+    fn give_shares(&self, opposite_ids: &[u16]) -> Vec<T> {
+        let xs: Vec<T> = opposite_ids.iter().map(|&id| id.into()).collect();
+        self.poly.as_ref().unwrap().eval_many(&xs)
     }
 
     fn recieve_share(&mut self, opposite_player: &Player<T>) {
@@ -76,182 +81,24 @@ where
 // parts stands for participants
 fn phis<T>(parts: &[u16]) -> HashMap<u16, T>
 where
-    T: NumAssign + From<u16> + fmt::Display + fmt::Debug + Clone + Copy + 'static,
+    T: Field,
 {
-    let mut res = HashMap::new();
-    for (i, &p_ognl) in parts.iter().enumerate() {
-        let p: T = p_ognl.into();
-        let mut r = T::one();
-        for (j, &q) in parts.iter().enumerate() {
-            let q: T = q.into();
-            if i == j {
-                continue;
-            }
-
-            if p == q {
-                panic!("Invalid participants");
-            }
-
-            r *= (T::zero() - q) / (p - q);
-        }
-        res.insert(p_ognl, r);
-    }
-    res
-}
-
-fn add_simulation_f64() {
-    let mut player1 = Player::new(1, 2.0, vec![5.0]);
-    let mut player2 = Player::new(2, 4.0, vec![3.0]);
-    let mut player3 = Player::new(3, 6.0, vec![7.0]); // player3 is the helper
-
-    player1.make_poly(2);
-    player2.make_poly(2);
-
-    // player1.recieve_share(&player1);
-    player1.recieve_share(&player2);
-    player2.recieve_share(&player1);
-    // player2.recieve_share(&player2);
-    player3.recieve_share(&player1);
-    player3.recieve_share(&player2);
-
-    let phs12 = phis(&[player1.id, player2.id]);
-    let phs13 = phis(&[player1.id, player3.id]);
-    let phs23 = phis(&[player2.id, player3.id]);
-
-    player1.fold_share(|shares| shares.values().sum::<f64>());
-    player2.fold_share(|shares| shares.values().sum::<f64>());
-    player3.fold_share(|shares| shares.values().sum::<f64>());
-
-    println!("p1: {:?}", player1);
-    println!("p2: {:?}", player2);
-    println!("p3: {:?}", player3);
-
-    // p1, p2
-    println!(
-        "[p1, p2] s_1 + s_2 = {}",
-        phs12.get(&player1.id).unwrap() * player1.folded_share
-            + phs12.get(&player2.id).unwrap() * player2.folded_share
-    );
-
-    // p1, p3
-    println!(
-        "[p1, p3] s_1 + s_2 = {}",
-        phs13.get(&player1.id).unwrap() * player1.folded_share
-            + phs13.get(&player3.id).unwrap() * player3.folded_share
-    );
-
-    // p2, p3
-    println!(
-        "[p2, p3] s_1 + s_2 = {}",
-        phs23.get(&player2.id).unwrap() * player2.folded_share
-            + phs23.get(&player3.id).unwrap() * player3.folded_share
-    );
-}
-
-fn mul_simulation_f64() {
-    let mut player1 = Player::new(1, 2.0, vec![5.0]);
-    let mut player2 = Player::new(2, 4.0, vec![3.0]);
-    let mut player3 = Player::new(3, 6.0, vec![7.0]); // player3 is the helper
-
-    player1.make_poly(2);
-    player2.make_poly(2);
-
-    // player1.recieve_share(&player1);
-    player1.recieve_share(&player2);
-    player2.recieve_share(&player1);
-    // player2.recieve_share(&player2);
-    player3.recieve_share(&player1);
-    player3.recieve_share(&player2);
-
-    player1.fold_share(|shares| shares.values().product::<f64>());
-    player2.fold_share(|shares| shares.values().product::<f64>());
-    player3.fold_share(|shares| shares.values().product::<f64>());
-
-    println!("p1: {:?}", player1);
-    println!("p2: {:?}", player2);
-    println!("p3: {:?}", player3);
-
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
-    let i1: u16 = rng.gen_range(0..17);
-    let i2: u16 = rng.gen_range(0..17);
-    let i3: u16 = rng.gen_range(0..17);
-    let mut player1_m = Player::new(1, player1.folded_share, vec![i1 as f64]);
-    let mut player2_m = Player::new(2, player2.folded_share, vec![i2 as f64]);
-    let mut player3_m = Player::new(3, player3.folded_share, vec![i3 as f64]);
-
-    player1_m.make_poly(2);
-    player2_m.make_poly(2);
-    player3_m.make_poly(2);
-
-    // player1_m.recieve_share(&player1_m);
-    player1_m.recieve_share(&player2_m);
-    player1_m.recieve_share(&player3_m);
-    player2_m.recieve_share(&player1_m);
-    // player2_m.recieve_share(&player2_m);
-    player2_m.recieve_share(&player3_m);
-    player3_m.recieve_share(&player1_m);
-    player3_m.recieve_share(&player2_m);
-    // player3_m.recieve_share(&player3_m);
-
-    let phs123 = phis(&[player1.id, player2.id, player3.id]);
-
-    player1_m.fold_share(|shares| {
-        shares
-            .iter()
-            .map(|(k, v)| phs123.get(k).unwrap() * v)
-            .sum::<f64>()
-    });
-    player2_m.fold_share(|shares| {
-        shares
-            .iter()
-            .map(|(k, v)| phs123.get(k).unwrap() * v)
-            .sum::<f64>()
-    });
-    player3_m.fold_share(|shares| {
-        shares
-            .iter()
-            .map(|(k, v)| phs123.get(k).unwrap() * v)
-            .sum::<f64>()
-    });
-
-    println!("p1m: {:?}", player1_m);
-    println!("p2m: {:?}", player2_m);
-    println!("p3m: {:?}", player3_m);
-
-    let phs12 = phis(&[player1.id, player2.id]);
-    let phs13 = phis(&[player1.id, player3.id]);
-    let phs23 = phis(&[player2.id, player3.id]);
-
-    // p1, p2
-    println!(
-        "[p1, p2] s_1 * s_2 = {}",
-        phs12.get(&player1_m.id).unwrap() * player1_m.folded_share
-            + phs12.get(&player2_m.id).unwrap() * player2_m.folded_share
-    );
-
-    // p1, p3
-    println!(
-        "[p1, p3] s_1 * s_2 = {}",
-        phs13.get(&player1_m.id).unwrap() * player1_m.folded_share
-            + phs13.get(&player3_m.id).unwrap() * player3_m.folded_share
-    );
-
-    // p2, p3
-    println!(
-        "[p2, p3] s_1 * s_2 = {}",
-        phs23.get(&player2_m.id).unwrap() * player2_m.folded_share
-            + phs23.get(&player3_m.id).unwrap() * player3_m.folded_share
-    );
+    let xs: Vec<T> = parts.iter().map(|&p| p.into()).collect();
+    parts
+        .iter()
+        .copied()
+        .zip(lagrange_coefficients(&xs))
+        .collect()
 }
 
-use shamir_share::ModInt;
+use shamir_share::{ModCom, ModInt};
 type M = ModInt<17>;
 
-fn add_simulation() {
-    let mut player1 = Player::new(1, M::new(2), vec![M::new(5)]);
-    let mut player2 = Player::new(2, M::new(4), vec![M::new(3)]);
-    let mut player3 = Player::new(3, M::new(6), vec![M::new(7)]); // player3 is the helper
+// 法を実行時に決める DynModInt でも同じコードで動く
+fn add_simulation<T: Field + std::iter::Sum>() {
+    let mut player1 = Player::new(1, T::from(2u16), vec![T::from(5u16)]);
+    let mut player2 = Player::new(2, T::from(4u16), vec![T::from(3u16)]);
+    let mut player3 = Player::new(3, T::from(6u16), vec![T::from(7u16)]); // player3 is the helper
 
     player1.make_poly(2);
     player2.make_poly(2);
@@ -263,13 +110,13 @@ fn add_simulation() {
     player3.recieve_share(&player1);
     player3.recieve_share(&player2);
 
-    let phs12: HashMap<u16, M> = phis(&[player1.id, player2.id]);
-    let phs13: HashMap<u16, M> = phis(&[player1.id, player3.id]);
-    let phs23: HashMap<u16, M> = phis(&[player2.id, player3.id]);
+    let phs12: HashMap<u16, T> = phis(&[player1.id, player2.id]);
+    let phs13: HashMap<u16, T> = phis(&[player1.id, player3.id]);
+    let phs23: HashMap<u16, T> = phis(&[player2.id, player3.id]);
 
-    player1.fold_share(|shares| shares.values().fold(M::zero(), |acc, &e| acc + e));
-    player2.fold_share(|shares| shares.values().fold(M::zero(), |acc, &e| acc + e));
-    player3.fold_share(|shares| shares.values().fold(M::zero(), |acc, &e| acc + e));
+    player1.fold_share(|shares| shares.values().copied().sum());
+    player2.fold_share(|shares| shares.values().copied().sum());
+    player3.fold_share(|shares| shares.values().copied().sum());
 
     println!("p1: {:?}", player1);
     println!("p2: {:?}", player2);
@@ -312,28 +159,19 @@ fn mul_simulation() {
     player3.recieve_share(&player1);
     player3.recieve_share(&player2);
 
-    player1.fold_share(|shares| shares.values().fold(M::one(), |acc, &e| acc * e));
-    player2.fold_share(|shares| shares.values().fold(M::one(), |acc, &e| acc * e));
-    player3.fold_share(|shares| shares.values().fold(M::one(), |acc, &e| acc * e));
+    player1.fold_share(|shares| shares.values().product());
+    player2.fold_share(|shares| shares.values().product());
+    player3.fold_share(|shares| shares.values().product());
 
     println!("p1: {:?}", player1);
     println!("p2: {:?}", player2);
     println!("p3: {:?}", player3);
 
-    /*
-    use rand::Rng;
+    // 次数削減用の多項式の係数は体全体から一様に選ぶ
     let mut rng = rand::thread_rng();
-    let i1: u16 = rng.gen_range(0..17);
-    let i2: u16 = rng.gen_range(0..17);
-    let i3: u16 = rng.gen_range(0..17);
-    */
-    // ↓検算のため固定
-    let i1: u16 = 7;
-    let i2: u16 = 9;
-    let i3: u16 = 11;
-    let mut player1_m = Player::new(1, player1.folded_share, vec![i1.into()]);
-    let mut player2_m = Player::new(2, player2.folded_share, vec![i2.into()]);
-    let mut player3_m = Player::new(3, player3.folded_share, vec![i3.into()]);
+    let mut player1_m = Player::new(1, player1.folded_share, M::random_vec(&mut rng, 1));
+    let mut player2_m = Player::new(2, player2.folded_share, M::random_vec(&mut rng, 1));
+    let mut player3_m = Player::new(3, player3.folded_share, M::random_vec(&mut rng, 1));
 
     player1_m.make_poly(2);
     player2_m.make_poly(2);
@@ -349,25 +187,27 @@ fn mul_simulation() {
     player3_m.recieve_share(&player2_m);
     // player3_m.recieve_share(&player3_m);
 
-    let phs123: HashMap<u16, M> = phis(&[player1.id, player2.id, player3.id]);
+    // id 1..=3 は連続しているので階乗の表から直接求まる
+    let mut com = ModCom::<17>::new(4);
+    let phs123: HashMap<u16, M> = (1..=3).zip(com.lagrange_coefficients(1..=3)).collect();
 
     player1_m.fold_share(|shares| {
         shares
             .iter()
             .map(|(k, &v)| *phs123.get(k).unwrap() * v)
-            .fold(M::zero(), |acc, e| acc + e)
+            .sum()
     });
     player2_m.fold_share(|shares| {
         shares
             .iter()
             .map(|(k, &v)| *phs123.get(k).unwrap() * v)
-            .fold(M::zero(), |acc, e| acc + e)
+            .sum()
     });
     player3_m.fold_share(|shares| {
         shares
             .iter()
             .map(|(k, &v)| *phs123.get(k).unwrap() * v)
-            .fold(M::zero(), |acc, e| acc + e)
+            .sum()
     });
 
     println!("p1m: {:?}", player1_m);
@@ -400,43 +240,230 @@ fn mul_simulation() {
     );
 }
 
-fn main() {
-    println!("Add simulation f64");
-    add_simulation_f64();
-    println!("Mul simulation f64");
-    mul_simulation_f64();
+use shamir_share::{Gf256, Gf2_128};
 
-    println!("==============================");
+fn byte_simulation() {
+    let mut rng = rand::thread_rng();
+    let secret = "Hello, Shamir!";
+
+    // 1バイトごとにplayer1が2-out-of-3でシェアを配る
+    let mut player2_shares = vec![];
+    let mut player3_shares = vec![];
+    for &b in secret.as_bytes() {
+        let mut dealer = Player::new(1, Gf256::new(b), Gf256::random_vec(&mut rng, 1));
+        dealer.make_poly(2);
+        player2_shares.push(dealer.give_share(2));
+        player3_shares.push(dealer.give_share(3));
+    }
+
+    println!(
+        "p2 shares: {:02x?}",
+        player2_shares.iter().map(|s| s.val()).collect::<Vec<_>>()
+    );
+    println!(
+        "p3 shares: {:02x?}",
+        player3_shares.iter().map(|s| s.val()).collect::<Vec<_>>()
+    );
+
+    let phs23: HashMap<u16, Gf256> = phis(&[2, 3]);
+    let recovered: Vec<u8> = player2_shares
+        .iter()
+        .zip(player3_shares.iter())
+        .map(|(&s2, &s3)| (phs23[&2] * s2 + phs23[&3] * s3).val())
+        .collect();
+
+    println!("[p2, p3] secret = {}", String::from_utf8_lossy(&recovered));
+}
+
+fn key_simulation() {
+    let mut rng = rand::thread_rng();
+    let key: u128 = 0x000102030405060708090a0b0c0d0e0f;
+
+    // 128bit鍵を1要素として3-out-of-4でシェアを配る
+    let mut dealer = Player::new(1, Gf2_128::new(key), Gf2_128::random_vec(&mut rng, 2));
+    dealer.make_poly(3);
+    let shares: HashMap<u16, Gf2_128> = [2, 3, 4]
+        .iter()
+        .map(|&id| (id, dealer.give_share(id)))
+        .collect();
+
+    println!(
+        "shares: {:032x?}",
+        shares.values().map(|s| s.val()).collect::<Vec<_>>()
+    );
 
+    let phs234: HashMap<u16, Gf2_128> = phis(&[2, 3, 4]);
+    let recovered = shares
+        .iter()
+        .fold(Gf2_128::zero(), |acc, (id, &s)| acc + phs234[id] * s);
+
+    println!("[p2, p3, p4] key = {:032x}", recovered.val());
+}
+
+use shamir_share::{SubproductTree, F61};
+
+fn committee_simulation() {
+    type P = F61;
+    let mut rng = rand::thread_rng();
+    let (n, k) = (2000u16, 1000);
+
+    // player1が1000-out-of-2000でシェアを配る
+    let mut dealer = Player::new(1, P::new(123_456_789), P::random_vec(&mut rng, k - 1));
+    dealer.make_poly(k);
+    let ids: Vec<u16> = (1..=n).collect();
+    let shares = dealer.give_shares(&ids);
+
+    // 後半の1000人で復元する
+    let parts = &ids[(n as usize - k)..];
+    let xs: Vec<P> = parts.iter().map(|&id| id.into()).collect();
+    let recovered = SubproductTree::new(&xs)
+        .lagrange_coefficients()
+        .iter()
+        .zip(shares[(n as usize - k)..].iter())
+        .map(|(&l, &s)| l * s)
+        .sum::<P>();
+
+    println!("[p1001, ..., p2000] secret = {}", recovered);
+}
+
+// 平均のような分数の結果も有理数復元で正確に取り出せる
+fn average_simulation() {
+    type P = F61;
+    let mut rng = rand::thread_rng();
+    // 2.5, 4, 1 を分数のまま埋め込む
+    let secrets = [
+        P::from_fraction(5, 2).unwrap(),
+        P::from_fraction(4, 1).unwrap(),
+        P::from_fraction(1, 1).unwrap(),
+    ];
+    let mut players: Vec<Player<P>> = secrets
+        .iter()
+        .enumerate()
+        .map(|(i, &s)| Player::new(i as u16 + 1, s, P::random_vec(&mut rng, 1)))
+        .collect();
+    for p in players.iter_mut() {
+        p.make_poly(2);
+    }
+    for i in 0..players.len() {
+        for j in 0..players.len() {
+            if i != j {
+                let share = players[j].give_share(players[i].id);
+                let id = players[j].id;
+                players[i].shares.insert(id, share);
+            }
+        }
+    }
+
+    // 各自のシェアの和を人数で割ったものが平均のシェアになる
+    let n_inv = P::new(players.len()).inv();
+    for p in players.iter_mut() {
+        p.fold_share(|shares| shares.values().copied().sum::<P>() * n_inv);
+    }
+
+    let phs12: HashMap<u16, P> = phis(&[1, 2]);
+    let avg = phs12[&1] * players[0].folded_share + phs12[&2] * players[1].folded_share;
+    match avg.to_fraction() {
+        Some((a, b)) => println!("[p1, p2] average = {}/{}", a, b),
+        None => println!("[p1, p2] average = {} (not a small fraction)", avg),
+    }
+}
+
+use shamir_share::{Rns2, F64};
+
+// 1つの法に収まらない秘密を剰余ごとに独立にシェアする
+fn rns_simulation() {
+    type R = Rns2<2_305_843_009_213_693_951, 18_446_744_073_709_551_557>;
+    let mut rng = rand::thread_rng();
+    let secret: u128 = (1 << 120) + 0x0123_4567_89ab_cdef;
+    let r = R::from_u128(secret);
+
+    let mut dealer1 = Player::new(1, r.r1, F61::random_vec(&mut rng, 1));
+    let mut dealer2 = Player::new(1, r.r2, F64::random_vec(&mut rng, 1));
+    dealer1.make_poly(2);
+    dealer2.make_poly(2);
+
+    // player2, player3 がそれぞれ両方の剰余のシェアを受け取る
+    let shares: Vec<R> = [2, 3]
+        .iter()
+        .map(|&id| R::new(dealer1.give_share(id), dealer2.give_share(id)))
+        .collect();
+
+    let phs1: HashMap<u16, F61> = phis(&[2, 3]);
+    let phs2: HashMap<u16, F64> = phis(&[2, 3]);
+    let recovered = R::new(
+        phs1[&2] * shares[0].r1 + phs1[&3] * shares[1].r1,
+        phs2[&2] * shares[0].r2 + phs2[&3] * shares[1].r2,
+    );
+
+    println!("secret    = {:#x}", secret);
+    match recovered.to_u128() {
+        Some(x) => println!("[p2, p3] secret = {:#x}", x),
+        None => println!("[p2, p3] secret does not fit in u128"),
+    }
+}
+
+use shamir_share::{mod_context, preset_prime, CtModInt, DynModInt, ModContext};
+
+mod_context!(Config);
+
+fn main() {
     println!("Add simulation Z_17");
-    add_simulation();
+    add_simulation::<M>();
+
+    // 法は環境変数 SHAMIR_MODULUS (素数) か SHAMIR_BITS (既定の素数のビット数) で指定できる
+    let modulus = std::env::var("SHAMIR_MODULUS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .or_else(|| {
+            let bits = std::env::var("SHAMIR_BITS").ok()?.parse().ok()?;
+            preset_prime(bits)
+        })
+        .unwrap_or(1_000_000_007);
+    Config::set_modulus(modulus);
+    println!("Add simulation Z_{} (runtime modulus)", Config::modulus());
+    add_simulation::<DynModInt<Config>>();
+
+    // 秘密やシェアの計算で値に依存した分岐をしない
+    println!("Add simulation Z_17 (constant-time)");
+    add_simulation::<CtModInt<17>>();
+
     println!("Mul simulation Z_17");
     mul_simulation();
+
+    println!("==============================");
+
+    println!("Byte simulation GF(2^8)");
+    byte_simulation();
+    println!("Key simulation GF(2^128)");
+    key_simulation();
+    println!("Committee simulation Z_(2^61-1)");
+    committee_simulation();
+    println!("Average simulation Z_(2^61-1)");
+    average_simulation();
+    println!("RNS simulation Z_(2^61-1) x Z_(2^64-59)");
+    rns_simulation();
 }
 
 /* its result is:
-Add simulation f64
-p1: Player { id: 1, secret: 2, rands: [5.0], shares: {1: 7.0, 2: 7.0}, folded_share: 14 }
-p2: Player { id: 2, secret: 4, rands: [3.0], shares: {1: 12.0, 2: 10.0}, folded_share: 22 }
-p3: Player { id: 3, secret: 6, rands: [7.0], shares: {1: 17.0, 2: 13.0}, folded_share: 30 }
+(p1m, p2m, p3m の乱数と GF(2^8), GF(2^128) のシェアは実行ごとに変わる。shares の表示順も不定)
+Add simulation Z_17
+p1: Player { id: 1, secret: 2, rands: [ModInt { val: 5 }], shares: {2: ModInt { val: 7 }, 1: ModInt { val: 7 }}, folded_share: 14 }
+p2: Player { id: 2, secret: 4, rands: [ModInt { val: 3 }], shares: {2: ModInt { val: 10 }, 1: ModInt { val: 12 }}, folded_share: 5 }
+p3: Player { id: 3, secret: 6, rands: [ModInt { val: 7 }], shares: {1: ModInt { val: 0 }, 2: ModInt { val: 13 }}, folded_share: 13 }
 [p1, p2] s_1 + s_2 = 6
 [p1, p3] s_1 + s_2 = 6
 [p2, p3] s_1 + s_2 = 6
-Mul simulation f64
-p1: Player { id: 1, secret: 2, rands: [5.0], shares: {1: 7.0, 2: 7.0}, folded_share: 49 }
-p2: Player { id: 2, secret: 4, rands: [3.0], shares: {2: 10.0, 1: 12.0}, folded_share: 120 }
-p3: Player { id: 3, secret: 6, rands: [7.0], shares: {1: 17.0, 2: 13.0}, folded_share: 221 }
-p1m: Player { id: 1, secret: 49, rands: [6.0], shares: {3: 223.0, 2: 126.0, 1: 55.0}, folded_share: 10 }
-p2m: Player { id: 2, secret: 120, rands: [6.0], shares: {2: 132.0, 1: 61.0, 3: 225.0}, folded_share: 12 }
-p3m: Player { id: 3, secret: 221, rands: [2.0], shares: {2: 138.0, 1: 67.0, 3: 227.0}, folded_share: 14 }
-[p1, p2] s_1 * s_2 = 8
-[p1, p3] s_1 * s_2 = 8
-[p2, p3] s_1 * s_2 = 8
-==============================
-Add simulation Z_17
-p1: Player { id: 1, secret: 2, rands: [ModInt { val: 5 }], shares: {1: ModInt { val: 7 }, 2: ModInt { val: 7 }}, folded_share: 14 }
-p2: Player { id: 2, secret: 4, rands: [ModInt { val: 3 }], shares: {2: ModInt { val: 10 }, 1: ModInt { val: 12 }}, folded_share: 5 }
-p3: Player { id: 3, secret: 6, rands: [ModInt { val: 7 }], shares: {2: ModInt { val: 13 }, 1: ModInt { val: 0 }}, folded_share: 13 }
+Add simulation Z_1000000007 (runtime modulus)
+p1: Player { id: 1, secret: 2, rands: [DynModInt { val: 5 }], shares: {2: DynModInt { val: 7 }, 1: DynModInt { val: 7 }}, folded_share: 14 }
+p2: Player { id: 2, secret: 4, rands: [DynModInt { val: 3 }], shares: {2: DynModInt { val: 10 }, 1: DynModInt { val: 12 }}, folded_share: 22 }
+p3: Player { id: 3, secret: 6, rands: [DynModInt { val: 7 }], shares: {1: DynModInt { val: 17 }, 2: DynModInt { val: 13 }}, folded_share: 30 }
+[p1, p2] s_1 + s_2 = 6
+[p1, p3] s_1 + s_2 = 6
+[p2, p3] s_1 + s_2 = 6
+Add simulation Z_17 (constant-time)
+p1: Player { id: 1, secret: 2, rands: [CtModInt { val: 5 }], shares: {1: CtModInt { val: 7 }, 2: CtModInt { val: 7 }}, folded_share: 14 }
+p2: Player { id: 2, secret: 4, rands: [CtModInt { val: 3 }], shares: {2: CtModInt { val: 10 }, 1: CtModInt { val: 12 }}, folded_share: 5 }
+p3: Player { id: 3, secret: 6, rands: [CtModInt { val: 7 }], shares: {2: CtModInt { val: 13 }, 1: CtModInt { val: 0 }}, folded_share: 13 }
 [p1, p2] s_1 + s_2 = 6
 [p1, p3] s_1 + s_2 = 6
 [p2, p3] s_1 + s_2 = 6
@@ -444,10 +471,25 @@ Mul simulation Z_17
 p1: Player { id: 1, secret: 2, rands: [ModInt { val: 5 }], shares: {1: ModInt { val: 7 }, 2: ModInt { val: 7 }}, folded_share: 15 }
 p2: Player { id: 2, secret: 4, rands: [ModInt { val: 3 }], shares: {2: ModInt { val: 10 }, 1: ModInt { val: 12 }}, folded_share: 1 }
 p3: Player { id: 3, secret: 6, rands: [ModInt { val: 7 }], shares: {2: ModInt { val: 13 }, 1: ModInt { val: 0 }}, folded_share: 0 }
-p1m: Player { id: 1, secret: 15, rands: [ModInt { val: 7 }], shares: {2: ModInt { val: 10 }, 3: ModInt { val: 11 }, 1: ModInt { val: 5 }}, folded_share: 13 }
-p2m: Player { id: 2, secret: 1, rands: [ModInt { val: 9 }], shares: {2: ModInt { val: 2 }, 1: ModInt { val: 12 }, 3: ModInt { val: 5 }}, folded_share: 1 }
-p3m: Player { id: 3, secret: 0, rands: [ModInt { val: 11 }], shares: {1: ModInt { val: 2 }, 3: ModInt { val: 16 }, 2: ModInt { val: 11 }}, folded_share: 6 }
+p1m: Player { id: 1, secret: 15, rands: [ModInt { val: 9 }], shares: {2: ModInt { val: 7 }, 1: ModInt { val: 7 }, 3: ModInt { val: 15 }}, folded_share: 15 }
+p2m: Player { id: 2, secret: 1, rands: [ModInt { val: 6 }], shares: {1: ModInt { val: 16 }, 3: ModInt { val: 13 }, 2: ModInt { val: 13 }}, folded_share: 5 }
+p3m: Player { id: 3, secret: 0, rands: [ModInt { val: 15 }], shares: {3: ModInt { val: 11 }, 1: ModInt { val: 8 }, 2: ModInt { val: 2 }}, folded_share: 12 }
 [p1, p2] s_1 * s_2 = 8
 [p1, p3] s_1 * s_2 = 8
 [p2, p3] s_1 * s_2 = 8
+==============================
+Byte simulation GF(2^8)
+p2 shares: [88, be, e5, 7d, 0a, 3d, 61, 59, d7, da, 4b, 9c, 0e, 74]
+p3 shares: [e8, 5e, 2c, f8, b5, b8, cc, 5c, 05, 0a, 58, 6b, 30, d3]
+[p2, p3] secret = Hello, Shamir!
+Key simulation GF(2^128)
+shares: [21a3725948d200ac3652397c81f9517d, 37d81837e7ddcc17b56b1ddc86a85ca9, 761770abd6897421941a843a1abca383]
+[p2, p3, p4] key = 000102030405060708090a0b0c0d0e0f
+Committee simulation Z_(2^61-1)
+[p1001, ..., p2000] secret = 123456789
+Average simulation Z_(2^61-1)
+[p1, p2] average = 5/2
+RNS simulation Z_(2^61-1) x Z_(2^64-59)
+secret    = 0x1000000000000000123456789abcdef
+[p2, p3] secret = 0x1000000000000000123456789abcdef
 */
@@ -0,0 +1,296 @@
+use crate::{add_mod, field, sub_mod, Field, ModInt};
+use num_traits::{
+    identities::{One, Zero},
+    Num,
+};
+use rand::Rng;
+use std::convert::{From, Into};
+use std::fmt;
+use std::ops;
+
+// R = 2^64 とするMontgomery表現の定数。MontInt と CtModInt で共有する
+pub(crate) struct MontParams<const MOD: usize>;
+
+impl<const MOD: usize> MontParams<MOD> {
+    // MOD * N_INV ≡ 1 (mod 2^64)
+    pub(crate) const N_INV: u64 = {
+        assert!(
+            MOD % 2 == 1 && MOD > 1,
+            "Montgomery form requires an odd modulus"
+        );
+        let m = MOD as u64;
+        let mut inv = m; // 2^3 まで正しい
+        let mut i = 0;
+        while i < 5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(m.wrapping_mul(inv)));
+            i += 1;
+        }
+        inv
+    };
+    // R mod MOD
+    pub(crate) const R1: u64 = ((u64::MAX as u128 % MOD as u128 + 1) % MOD as u128) as u64;
+    // R^2 mod MOD
+    pub(crate) const R2: u64 = ((u128::MAX % MOD as u128 + 1) % MOD as u128) as u64;
+
+    // t < MOD * 2^64 に対し t * R^{-1} ≡ hi - mn (mod MOD) となる (hi, mn)。
+    // 最後の引き算は呼び出し側で行う (定数時間版は分岐しない引き算を使う)
+    pub(crate) const fn redc_parts(t: u128) -> (u64, u64) {
+        let lo = t as u64;
+        let hi = (t >> 64) as u64;
+        let m = lo.wrapping_mul(Self::N_INV);
+        let mn = ((m as u128 * MOD as u128) >> 64) as u64;
+        (hi, mn)
+    }
+}
+
+// R = 2^64 としたMontgomery表現。MODは奇数である必要がある
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct MontInt<const MOD: usize> {
+    val: u64, // val = x * R mod MOD
+}
+
+impl<const MOD: usize> MontInt<MOD> {
+    // t * R^{-1} mod MOD (t < MOD * 2^64)
+    fn redc(t: u128) -> u64 {
+        let (hi, mn) = MontParams::<MOD>::redc_parts(t);
+        sub_mod(hi as usize, mn as usize, MOD) as u64
+    }
+
+    pub fn new(n: usize) -> Self {
+        Self {
+            val: Self::redc((n % MOD) as u128 * MontParams::<MOD>::R2 as u128),
+        }
+    }
+
+    pub fn val(&self) -> usize {
+        Self::redc(self.val as u128) as usize
+    }
+
+    pub fn pow_u(&self, mut n: usize) -> Self {
+        let mut val = *self;
+        let mut res = Self::one();
+        while n > 0 {
+            if n % 2 == 1 {
+                res *= val;
+            }
+            val *= val;
+            n /= 2;
+        }
+
+        res
+    }
+
+    pub fn pow(&self, other: Self) -> Self {
+        self.pow_u(other.val())
+    }
+
+    pub fn inv(&self) -> Self {
+        self.pow_u(MOD - 2)
+    }
+}
+
+impl<const MOD: usize> Field for MontInt<MOD> {
+    fn checked_inv(&self) -> Option<Self> {
+        let () = ModInt::<MOD>::ASSERT_PRIME;
+        if self.val == 0 {
+            None
+        } else {
+            Some(self.pow_u(MOD - 2))
+        }
+    }
+
+    fn characteristic() -> Vec<u8> {
+        ModInt::<MOD>::characteristic()
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::from(ModInt::<MOD>::random(rng))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        ModInt::<MOD>::from(*self).to_bytes()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        ModInt::<MOD>::from_bytes(bytes).map(Self::from)
+    }
+}
+
+impl<const MOD: usize> Num for MontInt<MOD> {
+    type FromStrRadixErr = <usize as Num>::FromStrRadixErr;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        let val = usize::from_str_radix(str, radix)?;
+        Ok(Self::new(val))
+    }
+}
+
+impl<const MOD: usize> fmt::Display for MontInt<MOD> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.val())
+    }
+}
+
+impl<const MOD: usize> fmt::Debug for MontInt<MOD> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MontInt {{ val: {} }}", self.val())
+    }
+}
+
+impl<const MOD: usize, I> From<I> for MontInt<MOD>
+where
+    I: Into<usize>,
+{
+    fn from(n: I) -> Self {
+        Self::new(n.into())
+    }
+}
+
+impl<const MOD: usize> From<ModInt<MOD>> for MontInt<MOD> {
+    fn from(n: ModInt<MOD>) -> Self {
+        Self::new(n.val())
+    }
+}
+
+impl<const MOD: usize> From<MontInt<MOD>> for ModInt<MOD> {
+    fn from(n: MontInt<MOD>) -> Self {
+        Self::new(n.val())
+    }
+}
+
+impl<const MOD: usize> ops::Add for MontInt<MOD> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            val: add_mod(self.val as usize, other.val as usize, MOD) as u64,
+        }
+    }
+}
+
+impl<const MOD: usize> ops::AddAssign for MontInt<MOD> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const MOD: usize> ops::Neg for MontInt<MOD> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            val: sub_mod(0, self.val as usize, MOD) as u64,
+        }
+    }
+}
+
+impl<const MOD: usize> ops::Sub for MontInt<MOD> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            val: sub_mod(self.val as usize, other.val as usize, MOD) as u64,
+        }
+    }
+}
+
+impl<const MOD: usize> ops::SubAssign for MontInt<MOD> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const MOD: usize> ops::Mul for MontInt<MOD> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            val: Self::redc(self.val as u128 * other.val as u128),
+        }
+    }
+}
+
+impl<const MOD: usize> ops::MulAssign for MontInt<MOD> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<const MOD: usize> ops::Div for MontInt<MOD> {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other.val == 0 {
+            panic!("0 division occured.");
+        }
+
+        self * other.inv()
+    }
+}
+
+impl<const MOD: usize> ops::DivAssign for MontInt<MOD> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+field::impl_field_rem!([const MOD: usize] MontInt<MOD>);
+
+impl<const MOD: usize> Zero for MontInt<MOD> {
+    fn zero() -> Self {
+        Self { val: 0 }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.val == 0
+    }
+}
+
+impl<const MOD: usize> One for MontInt<MOD> {
+    fn one() -> Self {
+        Self {
+            val: MontParams::<MOD>::R1,
+        }
+    }
+
+    fn is_one(&self) -> bool {
+        self.val == MontParams::<MOD>::R1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_modint() {
+        type Mont = MontInt<1_000_000_007>;
+        type Mint = ModInt<1_000_000_007>;
+
+        let xs = [0, 1, 2, 111111111, 222222222, 333333333, 1_000_000_006];
+        for &a in xs.iter() {
+            for &b in xs.iter() {
+                let (ma, mb) = (Mont::new(a), Mont::new(b));
+                let (ia, ib) = (Mint::new(a), Mint::new(b));
+                assert_eq!((ma + mb).val(), (ia + ib).val());
+                assert_eq!((ma - mb).val(), (ia - ib).val());
+                assert_eq!((ma * mb).val(), (ia * ib).val());
+                if b != 0 {
+                    assert_eq!((ma / mb).val(), (ia / ib).val());
+                }
+            }
+        }
+        assert_eq!(ModInt::from(Mont::new(12345)), Mint::new(12345));
+    }
+
+    #[test]
+    fn test_u64_boundary() {
+        type M64 = MontInt<18_446_744_073_709_551_557>; // 2^64 - 59
+        let max = M64::new(18_446_744_073_709_551_556); // -1
+
+        assert!((max * max).is_one());
+        assert_eq!((max + max).val(), 18_446_744_073_709_551_555);
+        assert_eq!(max.inv(), max);
+        assert_eq!((M64::new(3) / M64::new(7) * M64::new(7)).val(), 3);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{batch_inv, Field, Polynomial};
+
+// layers[0] は葉 x - x_i、上の層は隣り合う2つの積 (余った1つはそのまま持ち上げる)
+#[derive(Debug, Clone)]
+pub struct SubproductTree<T: Field> {
+    xs: Vec<T>,
+    layers: Vec<Vec<Polynomial<T>>>,
+}
+
+impl<T: Field> SubproductTree<T> {
+    pub fn new(xs: &[T]) -> Self {
+        let leaves: Vec<Polynomial<T>> = xs
+            .iter()
+            .map(|&x| Polynomial::new(vec![-x, T::one()]))
+            .collect();
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [l, r] => l * r,
+                    _ => pair[0].clone(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Self {
+            xs: xs.to_vec(),
+            layers,
+        }
+    }
+
+    pub fn points(&self) -> &[T] {
+        &self.xs
+    }
+
+    // Π (x - x_i)
+    pub fn root(&self) -> Polynomial<T> {
+        match self.layers.last().and_then(|l| l.first()) {
+            Some(root) => root.clone(),
+            None => Polynomial::constant(T::one()),
+        }
+    }
+
+    // 根から順に各節の多項式で割った余りを下ろしていく
+    pub fn evaluate(&self, f: &Polynomial<T>) -> Vec<T> {
+        if self.xs.is_empty() {
+            return vec![];
+        }
+        let depth = self.layers.len();
+        let mut rems = vec![f.div_rem(&self.layers[depth - 1][0]).1];
+        for layer in self.layers.iter().take(depth - 1).skip(1).rev() {
+            rems = layer
+                .iter()
+                .enumerate()
+                .map(|(i, node)| rems[i / 2].div_rem(node).1)
+                .collect();
+        }
+        // 葉 x - x_i での余りは値そのもの
+        self.xs
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| rems[i / 2].eval(x))
+            .collect()
+    }
+
+    // 1 / m'(x_i)。重複した点があればパニック
+    fn inv_weights(&self) -> Vec<T> {
+        let mut ws = self.evaluate(&self.root().derivative());
+        if ws.iter().any(|w| w.is_zero()) {
+            panic!("Invalid participants");
+        }
+        batch_inv(&mut ws);
+        ws
+    }
+
+    // f(x_i) = ys[i] となる次数 n - 1 以下の多項式
+    pub fn interpolate(&self, ys: &[T]) -> Polynomial<T> {
+        assert_eq!(
+            self.xs.len(),
+            ys.len(),
+            "points and values differ in length"
+        );
+        if ys.is_empty() {
+            return Polynomial::zero();
+        }
+        // f = Σ c_i m(x) / (x - x_i), c_i = y_i / m'(x_i) を葉から組み上げる
+        let mut polys: Vec<Polynomial<T>> = self
+            .inv_weights()
+            .iter()
+            .zip(ys.iter())
+            .map(|(&w, &y)| Polynomial::constant(w * y))
+            .collect();
+        for layer in self.layers[..self.layers.len() - 1].iter() {
+            polys = polys
+                .chunks(2)
+                .zip(layer.chunks(2))
+                .map(|(ps, nodes)| match (ps, nodes) {
+                    ([pl, pr], [nl, nr]) => &(pl * nr) + &(pr * nl),
+                    _ => ps[0].clone(),
+                })
+                .collect();
+        }
+        polys.pop().unwrap()
+    }
+
+    // 0 における Lagrange 係数 λ_i = m(0) / (-x_i m'(x_i))。lagrange_coefficients の高速版
+    pub fn lagrange_coefficients(&self) -> Vec<T> {
+        if self.xs.iter().any(|x| x.is_zero()) {
+            panic!("Invalid participants");
+        }
+        let mut denoms: Vec<T> = self.xs.iter().map(|&x| -x).collect();
+        batch_inv(&mut denoms);
+        let m0 = self.root().coeff(0);
+        self.inv_weights()
+            .iter()
+            .zip(denoms.iter())
+            .map(|(&w, &d)| m0 * w * d)
+            .collect()
+    }
+}
+
+impl<T: Field> Polynomial<T> {
+    pub fn eval_many(&self, xs: &[T]) -> Vec<T> {
+        SubproductTree::new(xs).evaluate(self)
+    }
+
+    pub fn interpolate_fast(points: &[(T, T)]) -> Self {
+        let (xs, ys): (Vec<T>, Vec<T>) = points.iter().copied().unzip();
+        SubproductTree::new(&xs).interpolate(&ys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lagrange_coefficients, ModInt};
+    type M = ModInt<1_000_000_007>;
+
+    #[test]
+    fn test_eval_many() {
+        let mut rng = rand::thread_rng();
+        let f = Polynomial::random(M::new(42), 199, &mut rng);
+        for n in [0, 1, 2, 3, 77, 300] {
+            let xs: Vec<M> = (1..=n).map(|i| M::new(i * i + 5)).collect();
+            let expected: Vec<M> = xs.iter().map(|&x| f.eval(x)).collect();
+            assert_eq!(f.eval_many(&xs), expected);
+        }
+    }
+
+    #[test]
+    fn test_interpolate_fast() {
+        let mut rng = rand::thread_rng();
+        let f = Polynomial::random(M::new(42), 256, &mut rng);
+        let xs: Vec<M> = (1..=257u16).map(M::from).collect();
+        let tree = SubproductTree::new(&xs);
+        let ys = tree.evaluate(&f);
+        assert_eq!(tree.interpolate(&ys), f);
+
+        let points: Vec<(M, M)> = xs[..5].iter().map(|&x| (x, f.eval(x))).collect();
+        assert_eq!(
+            Polynomial::interpolate_fast(&points),
+            Polynomial::interpolate(&points)
+        );
+
+        let small = SubproductTree::new(&xs[..20]);
+        assert_eq!(
+            small.lagrange_coefficients(),
+            lagrange_coefficients(&xs[..20])
+        );
+        let secret = tree
+            .lagrange_coefficients()
+            .iter()
+            .zip(ys.iter())
+            .fold(M::new(0), |acc, (&l, &y)| acc + l * y);
+        assert_eq!(secret, M::new(42));
+    }
+}
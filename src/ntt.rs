@@ -0,0 +1,158 @@
+use crate::ModInt;
+use num_traits::identities::{One, Zero};
+
+// 相異なる素因数。試し割りなので MOD - 1 が小さい素因数の積に近い (NTT向きの法など) ことを想定している
+pub(crate) fn prime_factors(mut n: usize) -> Vec<usize> {
+    let mut res = vec![];
+    let mut d = 2;
+    while d <= n / d {
+        if n.is_multiple_of(d) {
+            res.push(d);
+            while n.is_multiple_of(d) {
+                n /= d;
+            }
+        }
+        d += if d == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+        res.push(n);
+    }
+    res
+}
+
+impl<const MOD: usize> ModInt<MOD> {
+    // 乗法群 (Z/MOD)^* の生成元のうち最小のもの
+    pub fn primitive_root() -> Self {
+        let () = Self::ASSERT_PRIME;
+        if MOD == 2 {
+            return Self::one();
+        }
+        let factors = prime_factors(MOD - 1);
+        let mut g = Self::new(2);
+        while factors.iter().any(|&q| g.pow_u((MOD - 1) / q).is_one()) {
+            g += Self::one();
+        }
+        g
+    }
+
+    // 1の原始n乗根。nがMOD - 1を割り切らなければNone
+    pub fn root_of_unity(n: usize) -> Option<Self> {
+        if n == 0 || !(MOD - 1).is_multiple_of(n) {
+            return None;
+        }
+        Some(Self::primitive_root().pow_u((MOD - 1) / n))
+    }
+}
+
+fn ntt_with_root<const MOD: usize>(a: &mut [ModInt<MOD>], root: ModInt<MOD>) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let w = root.pow_u(n / len);
+        for chunk in a.chunks_mut(len) {
+            let mut wn = ModInt::one();
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for (x, y) in lo.iter_mut().zip(hi.iter_mut()) {
+                let u = *x;
+                let v = *y * wn;
+                *x = u + v;
+                *y = u - v;
+                wn *= w;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+// a[k] = Σ a_i ω^(ik)。長さは2冪で MOD - 1 を割り切ること
+pub fn ntt<const MOD: usize>(a: &mut [ModInt<MOD>]) {
+    let n = a.len();
+    assert!(n.is_power_of_two(), "NTT length must be a power of two");
+    let root = match ModInt::root_of_unity(n) {
+        Some(root) => root,
+        None => panic!("no {}-th root of unity modulo {}", n, MOD),
+    };
+    ntt_with_root(a, root);
+}
+
+pub fn intt<const MOD: usize>(a: &mut [ModInt<MOD>]) {
+    let n = a.len();
+    assert!(n.is_power_of_two(), "NTT length must be a power of two");
+    let root = match ModInt::root_of_unity(n) {
+        Some(root) => root,
+        None => panic!("no {}-th root of unity modulo {}", n, MOD),
+    };
+    ntt_with_root(a, root.inv());
+    let n_inv = ModInt::<MOD>::new(n).inv();
+    for x in a.iter_mut() {
+        *x *= n_inv;
+    }
+}
+
+// 係数 coeffs の多項式を ω^0, ω^1, ..., ω^(n-1) で評価する (i番目がω^iでのシェア)
+pub fn evaluate_at_roots<const MOD: usize>(coeffs: &[ModInt<MOD>], n: usize) -> Vec<ModInt<MOD>> {
+    assert!(coeffs.len() <= n, "polynomial degree must be less than n");
+    let mut a = coeffs.to_vec();
+    a.resize(n, ModInt::zero());
+    ntt(&mut a);
+    a
+}
+
+// evaluate_at_roots の逆。全n点の値から係数を復元する (secret は先頭)
+pub fn interpolate_from_roots<const MOD: usize>(values: &[ModInt<MOD>]) -> Vec<ModInt<MOD>> {
+    let mut a = values.to_vec();
+    intt(&mut a);
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    type M = ModInt<998_244_353>;
+
+    #[test]
+    fn test_roots() {
+        assert_eq!(M::primitive_root(), M::new(3));
+        assert_eq!(ModInt::<17>::primitive_root(), ModInt::new(3));
+        let w = M::root_of_unity(1 << 23).unwrap();
+        assert!(w.pow_u(1 << 23).is_one());
+        assert!(!w.pow_u(1 << 22).is_one());
+        assert_eq!(M::root_of_unity(5), None);
+
+        // Goldilocks素数 2^64 - 2^32 + 1
+        type G = ModInt<18_446_744_069_414_584_321>;
+        let w = G::root_of_unity(1 << 32).unwrap();
+        assert!(!w.pow_u(1 << 31).is_one());
+    }
+
+    #[test]
+    fn test_ntt_shares() {
+        let coeffs: Vec<M> = (0..5usize).map(|i| M::new(i * i * 31 + 7)).collect();
+        let n = 8;
+        let shares = evaluate_at_roots(&coeffs, n);
+
+        let w = M::root_of_unity(n).unwrap();
+        for (i, &s) in shares.iter().enumerate() {
+            let x = w.pow_u(i);
+            let naive = coeffs.iter().rev().fold(M::zero(), |acc, &c| acc * x + c);
+            assert_eq!(s, naive);
+        }
+
+        let recovered = interpolate_from_roots(&shares);
+        assert_eq!(&recovered[..5], &coeffs[..]);
+        assert!(recovered[5..].iter().all(|c| c.is_zero()));
+    }
+}
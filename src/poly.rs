@@ -0,0 +1,393 @@
+use crate::{lagrange_coefficients, Field};
+use rand::Rng;
+use std::ops;
+
+// これより短い係数列は筆算で掛ける・割る
+const KARATSUBA_THRESHOLD: usize = 32;
+
+fn mul_naive<T: Field>(a: &[T], b: &[T]) -> Vec<T> {
+    let mut res = vec![T::zero(); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            res[i + j] += x * y;
+        }
+    }
+    res
+}
+
+fn add_slices<T: Field>(a: &[T], b: &[T]) -> Vec<T> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut res = long.to_vec();
+    for (r, &x) in res.iter_mut().zip(short.iter()) {
+        *r += x;
+    }
+    res
+}
+
+// 長さ a.len() + b.len() - 1 の積 (末尾の零は除かない)
+pub(crate) fn mul_slices<T: Field>(a: &[T], b: &[T]) -> Vec<T> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return mul_naive(a, b);
+    }
+
+    // a = a0 + x^m a1, b = b0 + x^m b1
+    let m = a.len().max(b.len()).div_ceil(2);
+    let (a0, a1) = a.split_at(m.min(a.len()));
+    let (b0, b1) = b.split_at(m.min(b.len()));
+    let z0 = mul_slices(a0, b0);
+    let z2 = mul_slices(a1, b1);
+    let z1 = mul_slices(&add_slices(a0, a1), &add_slices(b0, b1));
+
+    // z1 - z0 - z2 の上位は打ち消し合って零になるので、はみ出した分は切り捨てる
+    let n = a.len() + b.len() - 1;
+    let mut res = vec![T::zero(); n.max(z1.len() + m).max(z2.len() + 2 * m)];
+    for (i, &c) in z0.iter().enumerate() {
+        res[i] += c;
+        res[i + m] -= c;
+    }
+    for (i, &c) in z2.iter().enumerate() {
+        res[i + 2 * m] += c;
+        res[i + m] -= c;
+    }
+    for (i, &c) in z1.iter().enumerate() {
+        res[i + m] += c;
+    }
+    res.truncate(n);
+    res
+}
+
+// f * g ≡ 1 (mod x^n) となるg。Newton法で精度を倍々にする
+fn inv_series<T: Field>(f: &[T], n: usize) -> Vec<T> {
+    let mut g = vec![f[0].inv()];
+    let mut k = 1;
+    while k < n {
+        k *= 2;
+        // g <- g (2 - f g)
+        let mut fg = mul_slices(&f[..f.len().min(k)], &g);
+        fg.truncate(k);
+        for c in fg.iter_mut() {
+            *c = -*c;
+        }
+        fg[0] += T::one() + T::one();
+        g = mul_slices(&g, &fg);
+        g.truncate(k);
+    }
+    g.truncate(n);
+    g
+}
+
+// 係数は低次から順に並べ、最高次の係数は非零に保つ (零多項式は空)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial<T: Field> {
+    coeffs: Vec<T>,
+}
+
+impl<T: Field> Polynomial<T> {
+    pub fn new(coeffs: Vec<T>) -> Self {
+        let mut res = Self { coeffs };
+        res.trim();
+        res
+    }
+
+    fn trim(&mut self) {
+        while self.coeffs.last().is_some_and(|c| c.is_zero()) {
+            self.coeffs.pop();
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self { coeffs: vec![] }
+    }
+
+    pub fn constant(c: T) -> Self {
+        Self::new(vec![c])
+    }
+
+    // 定数項がsecretで、その他の係数が一様乱数の degree 次多項式
+    pub fn random<R: Rng + ?Sized>(secret: T, degree: usize, rng: &mut R) -> Self {
+        let mut coeffs = vec![secret];
+        coeffs.extend(T::random_vec(rng, degree));
+        Self::new(coeffs)
+    }
+
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    pub fn coeff(&self, i: usize) -> T {
+        self.coeffs.get(i).copied().unwrap_or_else(T::zero)
+    }
+
+    // 零多項式はNone
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    // Horner法
+    pub fn eval(&self, x: T) -> T {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(T::zero(), |acc, &c| acc * x + c)
+    }
+
+    pub fn derivative(&self) -> Self {
+        let mut k = T::zero();
+        let coeffs = self
+            .coeffs
+            .iter()
+            .skip(1)
+            .map(|&c| {
+                k += T::one();
+                c * k
+            })
+            .collect();
+        Self::new(coeffs)
+    }
+
+    // self = q * d + r, deg r < deg d となる (q, r)
+    pub fn div_rem(&self, d: &Self) -> (Self, Self) {
+        if d.is_zero() {
+            panic!("0 division occured.");
+        }
+        let (n, m) = (self.coeffs.len(), d.coeffs.len());
+        if n < m {
+            return (Self::zero(), self.clone());
+        }
+        let qlen = n - m + 1;
+
+        let q = if qlen.min(m) < KARATSUBA_THRESHOLD {
+            let lead_inv = d.coeffs[m - 1].inv();
+            let mut r = self.coeffs.clone();
+            let mut q = vec![T::zero(); qlen];
+            for i in (0..qlen).rev() {
+                let c = r[i + m - 1] * lead_inv;
+                q[i] = c;
+                for (j, &dj) in d.coeffs.iter().enumerate() {
+                    r[i + j] -= c * dj;
+                }
+            }
+            q
+        } else {
+            // 係数を反転すると商は rev(self) / rev(d) mod x^qlen になる
+            let a_rev: Vec<T> = self.coeffs.iter().rev().take(qlen).copied().collect();
+            let d_rev: Vec<T> = d.coeffs.iter().rev().copied().collect();
+            let mut q = mul_slices(&a_rev, &inv_series(&d_rev, qlen));
+            q.truncate(qlen);
+            q.reverse();
+            q
+        };
+
+        let q = Self::new(q);
+        let r = self - &(&q * d);
+        (q, r)
+    }
+
+    pub fn scale(&self, c: T) -> Self {
+        Self::new(self.coeffs.iter().map(|&a| a * c).collect())
+    }
+
+    // 相異なる x_i を通る次数 n - 1 以下の多項式。
+    // Π(x - x_j) を一度作り、各基底はそれを (x - x_i) で組立除法して O(n^2) で求める
+    pub fn interpolate(points: &[(T, T)]) -> Self {
+        let n = points.len();
+        let mut full = vec![T::one()];
+        for &(xj, _) in points.iter() {
+            full.push(T::zero());
+            for k in (1..full.len()).rev() {
+                full[k] = full[k - 1] - full[k] * xj;
+            }
+            full[0] = -(full[0] * xj);
+        }
+
+        let mut res = vec![T::zero(); n];
+        let mut basis = vec![T::zero(); n];
+        for &(xi, yi) in points.iter() {
+            // basis = full / (x - x_i) = Π_{j≠i} (x - x_j)
+            let mut carry = T::zero();
+            for k in (0..n).rev() {
+                carry = full[k + 1] + carry * xi;
+                basis[k] = carry;
+            }
+            // Π_{j≠i} (x_i - x_j)
+            let denom = basis.iter().rev().fold(T::zero(), |acc, &c| acc * xi + c);
+            if denom.is_zero() {
+                panic!("Invalid participants");
+            }
+            let c = yi / denom;
+            for (r, &b) in res.iter_mut().zip(basis.iter()) {
+                *r += b * c;
+            }
+        }
+        Self::new(res)
+    }
+
+    // x = 0 での値だけなら多項式を作らずに済む
+    pub fn interpolate_at_zero(points: &[(T, T)]) -> T {
+        let xs: Vec<T> = points.iter().map(|&(x, _)| x).collect();
+        lagrange_coefficients(&xs)
+            .iter()
+            .zip(points.iter())
+            .fold(T::zero(), |acc, (&l, &(_, y))| acc + l * y)
+    }
+
+    // 係数を低次から順に Field::to_bytes で並べる
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.coeffs.iter().flat_map(|c| c.to_bytes()).collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let len = T::zero().to_bytes().len();
+        if !bytes.len().is_multiple_of(len) {
+            return None;
+        }
+        let coeffs = bytes
+            .chunks(len)
+            .map(T::from_bytes)
+            .collect::<Option<Vec<T>>>()?;
+        Some(Self::new(coeffs))
+    }
+}
+
+impl<'a, T: Field> ops::Add<&'a Polynomial<T>> for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn add(self, other: &Polynomial<T>) -> Polynomial<T> {
+        let n = self.coeffs.len().max(other.coeffs.len());
+        Polynomial::new((0..n).map(|i| self.coeff(i) + other.coeff(i)).collect())
+    }
+}
+
+impl<'a, T: Field> ops::Sub<&'a Polynomial<T>> for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn sub(self, other: &Polynomial<T>) -> Polynomial<T> {
+        let n = self.coeffs.len().max(other.coeffs.len());
+        Polynomial::new((0..n).map(|i| self.coeff(i) - other.coeff(i)).collect())
+    }
+}
+
+impl<'a, T: Field> ops::Mul<&'a Polynomial<T>> for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(self, other: &Polynomial<T>) -> Polynomial<T> {
+        Polynomial::new(mul_slices(&self.coeffs, &other.coeffs))
+    }
+}
+
+impl<T: Field> ops::Neg for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn neg(self) -> Polynomial<T> {
+        Polynomial::new(self.coeffs.iter().map(|&c| -c).collect())
+    }
+}
+
+impl<T: Field> ops::Add for Polynomial<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        &self + &other
+    }
+}
+
+impl<T: Field> ops::Sub for Polynomial<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        &self - &other
+    }
+}
+
+impl<T: Field> ops::Mul for Polynomial<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        &self * &other
+    }
+}
+
+impl<T: Field> ops::Neg for Polynomial<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        -&self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ModInt;
+    type M = ModInt<1_000_000_007>;
+
+    fn poly(cs: &[usize]) -> Polynomial<M> {
+        Polynomial::new(cs.iter().map(|&c| M::new(c)).collect())
+    }
+
+    #[test]
+    fn test_arith() {
+        let f = poly(&[1, 2, 3]); // 3x^2 + 2x + 1
+        let g = poly(&[5, 0, 1_000_000_004]); // -3x^2 + 5
+        assert_eq!(f.eval(M::new(10)), M::new(321));
+        assert_eq!((&f + &g), poly(&[6, 2]));
+        assert_eq!((&f + &g).degree(), Some(1));
+        assert_eq!(&f - &f, Polynomial::zero());
+        assert_eq!(Polynomial::<M>::zero().degree(), None);
+        assert_eq!((&f * &poly(&[1, 1])), poly(&[1, 3, 5, 3]));
+        assert_eq!(f.scale(M::new(2)), poly(&[2, 4, 6]));
+        assert_eq!(-f.clone() + f.clone(), Polynomial::zero());
+
+        let x = M::new(123_456);
+        assert_eq!((&f * &g).eval(x), f.eval(x) * g.eval(x));
+    }
+
+    #[test]
+    fn test_interpolate_and_bytes() {
+        let mut rng = rand::thread_rng();
+        let f = Polynomial::random(M::new(42), 4, &mut rng);
+        let points: Vec<(M, M)> = (1..=5u16)
+            .map(|i| (M::from(i), f.eval(M::from(i))))
+            .collect();
+        assert_eq!(Polynomial::interpolate(&points), f);
+        assert_eq!(Polynomial::interpolate_at_zero(&points), M::new(42));
+        assert!(Polynomial::<M>::interpolate(&[]).is_zero());
+
+        let g = Polynomial::random(M::new(7), 39, &mut rng);
+        let points: Vec<(M, M)> = (0..40u32)
+            .map(|i| M::from(i * i + 3))
+            .map(|x| (x, g.eval(x)))
+            .collect();
+        assert_eq!(Polynomial::interpolate(&points), g);
+
+        assert_eq!(Polynomial::from_bytes(&f.to_bytes()), Some(f));
+        assert_eq!(Polynomial::<M>::from_bytes(&[0, 1, 2]), None);
+    }
+
+    #[test]
+    fn test_karatsuba_and_division() {
+        let mut rng = rand::thread_rng();
+        let a = Polynomial::new(M::random_vec(&mut rng, 300));
+        let b = Polynomial::new(M::random_vec(&mut rng, 97));
+        let x = M::new(987_654);
+        assert_eq!(
+            mul_slices(a.coeffs(), b.coeffs()),
+            mul_naive(a.coeffs(), b.coeffs())
+        );
+        assert_eq!((&a * &b).eval(x), a.eval(x) * b.eval(x));
+
+        for d in [&b, &poly(&[3, 1]), &a] {
+            let (q, r) = a.div_rem(d);
+            assert!(r.degree() < d.degree());
+            assert_eq!(&(&q * d) + &r, a);
+        }
+        assert_eq!(poly(&[0, 1, 3, 1]).derivative(), poly(&[1, 6, 3]));
+    }
+}
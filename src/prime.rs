@@ -0,0 +1,169 @@
+use crate::{mul_mod, pow_mod, Curve25519, Fp256, Mersenne127, ModInt};
+use rand::Rng;
+
+// 64bit以下で決定的なMiller-Rabin法
+pub const fn is_prime(n: usize) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let small = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    let mut i = 0;
+    while i < small.len() {
+        if n.is_multiple_of(small[i]) {
+            return n == small[i];
+        }
+        i += 1;
+    }
+
+    let mut d = n - 1;
+    let mut s = 0;
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+    let bases = [2, 325, 9375, 28178, 450775, 9780504, 1795265022];
+    let mut i = 0;
+    while i < bases.len() {
+        let a = bases[i] % n;
+        i += 1;
+        if a == 0 {
+            continue;
+        }
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut r = 1;
+        while r < s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                break;
+            }
+            r += 1;
+        }
+        if r == s {
+            return false;
+        }
+    }
+    true
+}
+
+// 最上位ビットが立った bits ビットの素数 (2 <= bits <= 64)
+pub fn random_prime<R: Rng + ?Sized>(rng: &mut R, bits: u32) -> usize {
+    assert!((2..=64).contains(&bits), "bits must be between 2 and 64");
+    if bits == 2 {
+        return if rng.gen() { 2 } else { 3 };
+    }
+    let top = 1usize << (bits - 1);
+    let mask = usize::MAX >> (64 - bits);
+    loop {
+        let n = (rng.gen::<usize>() & mask) | top | 1;
+        if is_prime(n) {
+            return n;
+        }
+    }
+}
+
+// p = 2q + 1 (q も素数)
+pub const fn is_safe_prime(p: usize) -> bool {
+    p >= 5 && is_prime(p) && is_prime((p - 1) / 2)
+}
+
+// bits ビットの安全素数 (3 <= bits <= 64)
+pub fn random_safe_prime<R: Rng + ?Sized>(rng: &mut R, bits: u32) -> usize {
+    assert!((3..=64).contains(&bits), "bits must be between 3 and 64");
+    loop {
+        let q = random_prime(rng, bits - 1);
+        if is_prime(2 * q + 1) {
+            return 2 * q + 1;
+        }
+    }
+}
+
+// 安全性のビット数ごとの素数の法。ModInt / MontInt / CtModInt 向き
+// 2^31 - 1
+pub type F31 = ModInt<2_147_483_647>;
+// 2^61 - 1
+pub type F61 = ModInt<2_305_843_009_213_693_951>;
+// 2^64 - 59 (64bitに収まる最大の素数)
+pub type F64 = ModInt<18_446_744_073_709_551_557>;
+// 2^127 - 1
+pub type F127 = Fp256<Mersenne127>;
+// 2^255 - 19
+pub type F255 = Fp256<Curve25519>;
+
+// NTT向き。119 * 2^23 + 1
+pub type Ntt998244353 = ModInt<998_244_353>;
+// 2^64 - 2^32 + 1。2^32 次までの1の根を持つ
+pub type Goldilocks = ModInt<18_446_744_069_414_584_321>;
+
+const PRESETS: [(u32, usize); 3] = [
+    (31, 2_147_483_647),
+    (61, 2_305_843_009_213_693_951),
+    (64, 18_446_744_073_709_551_557),
+];
+
+// 少なくとも bits ビットある最小の既定の素数。DynModInt の法の選択用。64bitを超えるならNone
+pub fn preset_prime(bits: u32) -> Option<usize> {
+    PRESETS.iter().find(|&&(b, _)| b >= bits).map(|&(_, p)| p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Field;
+    use num_traits::identities::One;
+
+    #[test]
+    fn test_is_prime() {
+        let n = 10_000;
+        let mut sieve = vec![true; n];
+        sieve[0] = false;
+        sieve[1] = false;
+        for i in 2..n {
+            if sieve[i] {
+                for j in (i * i..n).step_by(i) {
+                    sieve[j] = false;
+                }
+            }
+        }
+        for (i, &p) in sieve.iter().enumerate() {
+            assert_eq!(is_prime(i), p, "{}", i);
+        }
+
+        // Carmichael数と底2,3,5,7の強擬素数
+        for &c in [561, 41041, 3_215_031_751, 3_825_123_056_546_413_051].iter() {
+            assert!(!is_prime(c));
+        }
+        for &p in PRESETS.iter().map(|(_, p)| p) {
+            assert!(is_prime(p));
+        }
+        assert!(is_prime(998_244_353));
+        assert!(is_prime(18_446_744_069_414_584_321));
+        assert!(!is_prime(usize::MAX));
+    }
+
+    #[test]
+    fn test_random_prime_and_presets() {
+        let mut rng = rand::thread_rng();
+        for bits in 2..=64 {
+            let p = random_prime(&mut rng, bits);
+            assert!(is_prime(p));
+            assert_eq!(usize::BITS - p.leading_zeros(), bits);
+        }
+
+        assert_eq!(preset_prime(20), Some(2_147_483_647));
+        assert_eq!(preset_prime(61), Some(2_305_843_009_213_693_951));
+        assert_eq!(preset_prime(128), None);
+
+        // 2^127 ≡ 1 (mod 2^127 - 1)
+        assert!(F127::new(2).pow_u(127).is_one());
+        let limbs = <F127 as Field>::random(&mut rng).to_limbs();
+        assert_eq!(limbs[2..], [0, 0]);
+        assert_eq!(limbs[1] >> 63, 0);
+        assert!(F255::new(2).pow_u(255) == F255::new(19));
+        assert!(Goldilocks::root_of_unity(1 << 32).is_some());
+        assert!(Ntt998244353::root_of_unity(1 << 23).is_some());
+        assert_eq!([F31::IS_PRIME, F61::IS_PRIME, F64::IS_PRIME], [true; 3]);
+    }
+}
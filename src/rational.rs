@@ -0,0 +1,94 @@
+use crate::ModInt;
+
+const fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+impl<const MOD: usize> ModInt<MOD> {
+    // num / den。den が MOD の倍数ならNone
+    pub fn from_fraction(num: i128, den: i128) -> Option<Self> {
+        let inv = Self::from(den).checked_inv()?;
+        Some(Self::from(num) * inv)
+    }
+
+    // a / b ≡ self, |a| <= num_bound, 0 < b <= den_bound, gcd(a, b) = 1 となる (a, b)。
+    // 2 * num_bound * den_bound < MOD なら解は高々1つ
+    pub fn rational_reconstruct(&self, num_bound: usize, den_bound: usize) -> Option<(i128, u128)> {
+        // 拡張Euclidを r <= num_bound になった時点で打ち切る (r ≡ t * self)
+        let (mut r0, mut r1) = (MOD as i128, self.val() as i128);
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 > num_bound as i128 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+
+        let (a, b) = if t1 < 0 { (-r1, -t1) } else { (r1, t1) };
+        if b == 0 || b as u128 > den_bound as u128 || gcd(a.unsigned_abs(), b as u128) != 1 {
+            return None;
+        }
+        Some((a, b as u128))
+    }
+
+    // 分子・分母の上限を等しく floor(sqrt((MOD - 1) / 2)) とした場合
+    pub fn to_fraction(&self) -> Option<(i128, u128)> {
+        let bound = ((MOD - 1) / 2).isqrt();
+        self.rational_reconstruct(bound, bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::identities::Zero;
+    type M = ModInt<2_305_843_009_213_693_951>; // 2^61 - 1
+
+    #[test]
+    fn test_roundtrip() {
+        for &(a, b) in [
+            (7, 3),
+            (-7, 3),
+            (0, 1),
+            (1, 1),
+            (-1, 1),
+            (355, 113),
+            (-1_000_000, 999_999),
+        ]
+        .iter()
+        {
+            let x = M::from_fraction(a, b).unwrap();
+            assert_eq!(x.to_fraction(), Some((a, b as u128)));
+        }
+        // 既約でない入力も既約分数に戻る
+        assert_eq!(M::from_fraction(6, 4).unwrap().to_fraction(), Some((3, 2)));
+        assert_eq!(
+            M::from_fraction(6, -4).unwrap().to_fraction(),
+            Some((-3, 2))
+        );
+        assert_eq!(M::from_fraction(1, 0), None);
+        assert_eq!(M::zero().to_fraction(), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_bounds() {
+        type Small = ModInt<10_007>;
+        // 上限を満たさない場合はNone
+        let x = Small::from_fraction(50, 51).unwrap();
+        assert_eq!(x.rational_reconstruct(70, 70), Some((50, 51)));
+        assert_eq!(x.rational_reconstruct(40, 70), None);
+
+        // 平均 (2 + 4 + 1) / 3
+        let sum = M::new(2) + M::new(4) + M::new(1);
+        let avg = sum / M::new(3);
+        assert_eq!(avg.to_fraction(), Some((7, 3)));
+
+        // 上限を超える分数は元の値には戻らない
+        let big = M::from_fraction(1 << 40, (1 << 40) + 1).unwrap();
+        assert_ne!(big.to_fraction(), Some((1 << 40, (1 << 40) + 1)));
+    }
+}
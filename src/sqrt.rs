@@ -0,0 +1,175 @@
+use crate::ModInt;
+use num_traits::identities::{One, Zero};
+
+impl<const MOD: usize> ModInt<MOD> {
+    // Legendre記号 (val / MOD)。MODは素数
+    pub fn legendre(&self) -> i8 {
+        let () = Self::ASSERT_PRIME;
+        if MOD == 2 {
+            return self.val as i8;
+        }
+        let r = self.pow_u((MOD - 1) / 2);
+        if r.is_zero() {
+            0
+        } else if r.is_one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    // Jacobi記号 (val / MOD)。MODは奇数であれば合成数でもよい
+    pub fn jacobi(&self) -> i8 {
+        assert!(MOD % 2 == 1, "Jacobi symbol requires an odd modulus");
+        let (mut a, mut n) = (self.val, MOD);
+        let mut res = 1;
+        while a != 0 {
+            while a % 2 == 0 {
+                a /= 2;
+                if n % 8 == 3 || n % 8 == 5 {
+                    res = -res;
+                }
+            }
+            std::mem::swap(&mut a, &mut n);
+            if a % 4 == 3 && n % 4 == 3 {
+                res = -res;
+            }
+            a %= n;
+        }
+        if n == 1 {
+            res
+        } else {
+            0
+        }
+    }
+
+    // x^2 = val となるxのうち小さい方。平方非剰余ならNone
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.val == 0 || MOD == 2 {
+            return Some(*self);
+        }
+        if self.legendre() != 1 {
+            return None;
+        }
+
+        let root = if MOD % 4 == 3 {
+            self.pow_u((MOD + 1) / 4)
+        } else {
+            // MOD - 1 = q * 2^s。sが大きいとTonelli-ShanksはO(s^2)回の乗算になるのでCipollaに切り替える
+            let s = (MOD - 1).trailing_zeros() as usize;
+            let bits = (usize::BITS - MOD.leading_zeros()) as usize;
+            if s * (s - 1) > 8 * bits + 20 {
+                self.sqrt_cipolla()
+            } else {
+                self.sqrt_tonelli_shanks()
+            }
+        };
+
+        let neg = -root;
+        Some(if neg.val < root.val { neg } else { root })
+    }
+
+    fn non_residue() -> Self {
+        let mut z = Self::new(2);
+        while z.legendre() != -1 {
+            z += Self::one();
+        }
+        z
+    }
+
+    fn sqrt_tonelli_shanks(&self) -> Self {
+        let s = (MOD - 1).trailing_zeros();
+        let q = (MOD - 1) >> s;
+
+        let mut m = s;
+        let mut c = Self::non_residue().pow_u(q);
+        let mut t = self.pow_u(q);
+        let mut r = self.pow_u(q.div_ceil(2));
+        while !t.is_one() {
+            // t^(2^i) = 1 となる最小のi
+            let mut i = 0;
+            let mut t2 = t;
+            while !t2.is_one() {
+                t2 *= t2;
+                i += 1;
+            }
+            let b = c.pow_u(1 << (m - i - 1));
+            m = i;
+            c = b * b;
+            t *= c;
+            r *= b;
+        }
+        r
+    }
+
+    // (a + √w)^((MOD + 1) / 2) を F_MOD(√w) で計算する。ただし w = a^2 - val は平方非剰余
+    fn sqrt_cipolla(&self) -> Self {
+        let mut a = Self::zero();
+        let w = loop {
+            let w = a * a - *self;
+            if w.legendre() == -1 {
+                break w;
+            }
+            a += Self::one();
+        };
+
+        let mul = |(x0, x1): (Self, Self), (y0, y1): (Self, Self)| {
+            (x0 * y0 + x1 * y1 * w, x0 * y1 + x1 * y0)
+        };
+        let mut base = (a, Self::one());
+        let mut res = (Self::one(), Self::zero());
+        let mut n = MOD.div_ceil(2);
+        while n > 0 {
+            if n % 2 == 1 {
+                res = mul(res, base);
+            }
+            base = mul(base, base);
+            n /= 2;
+        }
+        debug_assert!(res.1.is_zero());
+        res.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbols() {
+        type M17 = ModInt<17>;
+        let residues: Vec<usize> = (1..17).filter(|&x| M17::new(x).legendre() == 1).collect();
+        assert_eq!(residues, vec![1, 2, 4, 8, 9, 13, 15, 16]);
+        assert_eq!(M17::zero().legendre(), 0);
+
+        // (x / 15) = (x / 3)(x / 5)
+        for x in 0..15 {
+            let expected = ModInt::<3>::new(x).legendre() * ModInt::<5>::new(x).legendre();
+            assert_eq!(ModInt::<15>::new(x).jacobi(), expected);
+        }
+        for x in 0..17 {
+            assert_eq!(M17::new(x).jacobi(), M17::new(x).legendre());
+        }
+    }
+
+    #[test]
+    fn test_sqrt() {
+        fn check<const MOD: usize>(xs: &[usize]) {
+            for &x in xs {
+                let a = ModInt::<MOD>::new(x);
+                let sq = a * a;
+                let r = sq.sqrt().unwrap();
+                assert_eq!(r * r, sq);
+                assert!(r.val <= MOD / 2);
+            }
+        }
+        check::<17>(&[0, 1, 2, 3, 5, 8, 16]); // Tonelli-Shanks
+        check::<1_000_000_007>(&[1, 12345, 999_999_999]); // MOD ≡ 3 (mod 4)
+        check::<998_244_353>(&[1, 3, 12345, 998_244_352]); // Cipolla
+        check::<18_446_744_073_709_551_557>(&[2, 1 << 63]);
+
+        assert_eq!(ModInt::<17>::new(3).sqrt(), None);
+        assert_eq!(ModInt::<998_244_353>::new(3).sqrt(), None);
+        assert_eq!(ModInt::<2>::new(1).sqrt(), Some(ModInt::new(1)));
+    }
+}